@@ -0,0 +1,12 @@
+fn main() {
+    cc::Build::new()
+        .file("BLAKE2/blake2b-ref.c")
+        .file("BLAKE2/blake2s-ref.c")
+        .file("BLAKE2/blake2xb.c")
+        .file("BLAKE2/blake2xs.c")
+        .file("src/glue.c")
+        .include("BLAKE2")
+        .compile("blake2ref");
+    println!("cargo:rerun-if-changed=BLAKE2");
+    println!("cargo:rerun-if-changed=src/glue.c");
+}
@@ -0,0 +1,63 @@
+/* Heap-allocated reference states initialized from a raw parameter block, so that Rust needs no struct layouts. */
+
+#include <stdlib.h>
+#include <string.h>
+#include "blake2.h"
+
+void *ref_blake2b_new(const uint8_t param[64], const uint8_t *key, size_t keylen, int last_node) {
+  blake2b_state *S = malloc(sizeof(blake2b_state));
+  blake2b_param P[1];
+  memcpy(P, param, sizeof(P));
+  blake2b_init_param(S, P);
+  S->last_node = (uint8_t)last_node;
+  if (keylen > 0) {
+    uint8_t block[BLAKE2B_BLOCKBYTES] = {0};
+    memcpy(block, key, keylen);
+    blake2b_update(S, block, BLAKE2B_BLOCKBYTES);
+  }
+  return S;
+}
+
+void ref_blake2b_update(void *S, const uint8_t *in, size_t inlen) { blake2b_update(S, in, inlen); }
+
+void ref_blake2b_final(void *S, uint8_t *out, size_t outlen) {
+  blake2b_final(S, out, outlen);
+  free(S);
+}
+
+void *ref_blake2s_new(const uint8_t param[32], const uint8_t *key, size_t keylen, int last_node) {
+  blake2s_state *S = malloc(sizeof(blake2s_state));
+  blake2s_param P[1];
+  memcpy(P, param, sizeof(P));
+  blake2s_init_param(S, P);
+  S->last_node = (uint8_t)last_node;
+  if (keylen > 0) {
+    uint8_t block[BLAKE2S_BLOCKBYTES] = {0};
+    memcpy(block, key, keylen);
+    blake2s_update(S, block, BLAKE2S_BLOCKBYTES);
+  }
+  return S;
+}
+
+void ref_blake2s_update(void *S, const uint8_t *in, size_t inlen) { blake2s_update(S, in, inlen); }
+
+void ref_blake2s_final(void *S, uint8_t *out, size_t outlen) {
+  blake2s_final(S, out, outlen);
+  free(S);
+}
+
+int ref_blake2xb(uint8_t *out, size_t outlen, size_t xoflen, const uint8_t *in, size_t inlen, const uint8_t *key,
+                 size_t keylen) {
+  blake2xb_state S[1];
+  if (blake2xb_init_key(S, xoflen, keylen ? key : NULL, keylen) < 0) return -1;
+  blake2xb_update(S, in, inlen);
+  return blake2xb_final(S, out, outlen);
+}
+
+int ref_blake2xs(uint8_t *out, size_t outlen, size_t xoflen, const uint8_t *in, size_t inlen, const uint8_t *key,
+                 size_t keylen) {
+  blake2xs_state S[1];
+  if (blake2xs_init_key(S, xoflen, keylen ? key : NULL, keylen) < 0) return -1;
+  blake2xs_update(S, in, inlen);
+  return blake2xs_final(S, out, outlen);
+}
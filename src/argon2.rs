@@ -0,0 +1,542 @@
+//! Argon2d, Argon2i and Argon2id of RFC 9106, version 0x13. The memory is a slice of blocks, which the caller provides
+//! without std. With std, hash() allocates it, lanes may be filled on several threads, and passwords are hashed to and
+//! verified against PHC strings.
+
+use crate::blake2b::{blake2b, blake2b_long, blake2b_params};
+
+/// Words of a block.
+const ARGON2_QQ: usize = 128;
+/// Bytes of a block.
+const ARGON2_BB: usize = 1024;
+/// Slices of a pass.
+const ARGON2_SYNC_POINTS: u32 = 4;
+/// The version number, 0x13.
+pub const ARGON2_VERSION: u32 = 0x13;
+
+/// The variant of Argon2, with its type number as the discriminant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argon2Type {
+    Argon2d = 0,
+    Argon2i = 1,
+    Argon2id = 2,
+}
+
+impl Argon2Type {
+    /// Name in PHC strings: "argon2d", "argon2i" or "argon2id".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Argon2d => "argon2d",
+            Self::Argon2i => "argon2i",
+            Self::Argon2id => "argon2id",
+        }
+    }
+
+    /// Find the variant by its name in PHC strings.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "argon2d" => Some(Self::Argon2d),
+            "argon2i" => Some(Self::Argon2i),
+            "argon2id" => Some(Self::Argon2id),
+            _ => None,
+        }
+    }
+}
+
+/// Errors of Argon2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argon2Error {
+    /// The tag is shorter than 4 bytes.
+    Output,
+    /// The salt is shorter than 8 bytes.
+    Salt,
+    /// The password, salt, secret or associated data is longer than 2^32 - 1 bytes.
+    Input,
+    /// The memory cost is below 8 KiB per lane.
+    Memory,
+    /// The time cost is zero.
+    Time,
+    /// The lane count is not in [1, 2^24 - 1].
+    Lanes,
+    /// The memory given to hash_with_memory() has fewer blocks than memory_blocks().
+    Blocks,
+    /// A PHC string is malformed or has unsupported parameters.
+    Encoding,
+}
+
+impl core::fmt::Display for Argon2Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Self::Output => "tag shorter than 4 bytes",
+            Self::Salt => "salt shorter than 8 bytes",
+            Self::Input => "input longer than 2^32 - 1 bytes",
+            Self::Memory => "memory cost below 8 KiB per lane",
+            Self::Time => "time cost is zero",
+            Self::Lanes => "lane count not in [1, 2^24 - 1]",
+            Self::Blocks => "not enough memory blocks",
+            Self::Encoding => "malformed PHC string",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for Argon2Error {}
+
+/// A block of 1 KiB, the unit of Argon2 memory.
+#[derive(Clone)]
+pub struct Argon2Block([u64; ARGON2_QQ]);
+
+impl Default for Argon2Block {
+    fn default() -> Self {
+        Self([0; ARGON2_QQ])
+    }
+}
+
+impl Argon2Block {
+    fn from_bytes(b: &[u8; ARGON2_BB]) -> Self {
+        let mut r = Self::default();
+        for (w, c) in r.0.iter_mut().zip(b.chunks_exact(8)) {
+            *w = u64::from_le_bytes(c.try_into().unwrap());
+        }
+        r
+    }
+
+    fn to_bytes(&self) -> [u8; ARGON2_BB] {
+        let mut r = [0; ARGON2_BB];
+        for (c, w) in r.chunks_exact_mut(8).zip(self.0) {
+            c.copy_from_slice(&w.to_le_bytes());
+        }
+        r
+    }
+}
+
+/// The multiplication-hardened addition of BlaMka.
+fn blamka(a: u64, b: u64) -> u64 {
+    let m = (a & 0xffff_ffff) * (b & 0xffff_ffff);
+    a.wrapping_add(b).wrapping_add(m.wrapping_mul(2))
+}
+
+fn gb(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize) {
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = blamka(v[a], v[b]);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = blamka(v[c], v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// The permutation P over eight 16-byte registers.
+fn permute(v: &mut [u64; 16]) {
+    gb(v, 0, 4, 8, 12);
+    gb(v, 1, 5, 9, 13);
+    gb(v, 2, 6, 10, 14);
+    gb(v, 3, 7, 11, 15);
+    gb(v, 0, 5, 10, 15);
+    gb(v, 1, 6, 11, 12);
+    gb(v, 2, 7, 8, 13);
+    gb(v, 3, 4, 9, 14);
+}
+
+/// The compression function G: P on the rows, then on the columns of X xor Y, xor X xor Y.
+fn compress(x: &Argon2Block, y: &Argon2Block) -> Argon2Block {
+    let mut r = [0; ARGON2_QQ];
+    r.iter_mut().zip(x.0.iter().zip(y.0)).for_each(|(r, (x, y))| *r = x ^ y);
+    let mut z = r;
+    for row in z.chunks_exact_mut(16) {
+        let v: &mut [u64; 16] = row.try_into().unwrap();
+        permute(v);
+    }
+    for i in 0..8 {
+        let mut v = [0; 16];
+        for j in 0..8 {
+            v[2 * j] = z[2 * i + 16 * j];
+            v[2 * j + 1] = z[2 * i + 16 * j + 1];
+        }
+        permute(&mut v);
+        for j in 0..8 {
+            z[2 * i + 16 * j] = v[2 * j];
+            z[2 * i + 16 * j + 1] = v[2 * j + 1];
+        }
+    }
+    z.iter_mut().zip(r).for_each(|(z, r)| *z ^= r);
+    Argon2Block(z)
+}
+
+/// Memory as seen while filling a segment: any block may be read, and the blocks of the segment written.
+trait Lanes {
+    fn block(&self, lane: u32, col: u32) -> &Argon2Block;
+    fn block_mut(&mut self, lane: u32, col: u32) -> &mut Argon2Block;
+}
+
+/// All of the memory, for filling segments one after another.
+struct Whole<'a> {
+    m: &'a mut [Argon2Block],
+    q: u32,
+}
+
+impl Lanes for Whole<'_> {
+    fn block(&self, lane: u32, col: u32) -> &Argon2Block {
+        &self.m[(lane * self.q + col) as usize]
+    }
+
+    fn block_mut(&mut self, lane: u32, col: u32) -> &mut Argon2Block {
+        &mut self.m[(lane * self.q + col) as usize]
+    }
+}
+
+/// The blocks before and after the current segment of a lane.
+#[cfg(feature = "std")]
+type Around<'a> = (&'a [Argon2Block], &'a [Argon2Block]);
+
+/// One segment and a shared view of the rest of the memory, for filling the segments of a slice on several threads.
+/// Argon2 never references the current segment of another lane.
+#[cfg(feature = "std")]
+struct Split<'a> {
+    around: &'a [Around<'a>],
+    seg: &'a mut [Argon2Block],
+    start: u32,
+}
+
+#[cfg(feature = "std")]
+impl Lanes for Split<'_> {
+    fn block(&self, lane: u32, col: u32) -> &Argon2Block {
+        let (a, b) = self.around[lane as usize];
+        if col < self.start {
+            &a[col as usize]
+        } else if col - self.start < self.seg.len() as u32 {
+            &self.seg[(col - self.start) as usize]
+        } else {
+            &b[(col - self.start) as usize - self.seg.len()]
+        }
+    }
+
+    fn block_mut(&mut self, _: u32, col: u32) -> &mut Argon2Block {
+        &mut self.seg[(col - self.start) as usize]
+    }
+}
+
+/// Argon2 parameters. The salt and password are given when hashing.
+#[derive(Clone)]
+pub struct Argon2<'a> {
+    y: Argon2Type,
+    m: u32,
+    t: u32,
+    p: u32,
+    k: &'a [u8],
+    x: &'a [u8],
+    #[cfg(feature = "std")]
+    threads: u32,
+}
+
+impl<'a> Argon2<'a> {
+    /// Set memory cost in KiB, at least 8 per lane.
+    pub fn memory(&mut self, m: u32) {
+        self.m = m;
+    }
+
+    /// Set time cost, the number of passes over the memory.
+    pub fn time(&mut self, t: u32) {
+        self.t = t;
+    }
+
+    /// Set degree of parallelism, the number of lanes.
+    pub fn lanes(&mut self, p: u32) {
+        self.p = p;
+    }
+
+    /// Set secret value K.
+    pub fn secret(&mut self, k: &'a [u8]) {
+        self.k = k;
+    }
+
+    /// Set associated data X.
+    pub fn data(&mut self, x: &'a [u8]) {
+        self.x = x;
+    }
+
+    /// Set the number of threads filling lanes. The tag does not depend on it.
+    #[cfg(feature = "std")]
+    pub fn threads(&mut self, n: u32) {
+        assert!(n >= 1);
+        self.threads = n;
+    }
+
+    /// Blocks of memory used: the memory cost rounded down to a multiple of 4 times the lane count.
+    pub fn memory_blocks(&self) -> usize {
+        let p = self.p.max(1);
+        (self.m / (ARGON2_SYNC_POINTS * p) * ARGON2_SYNC_POINTS * p) as usize
+    }
+
+    fn check(&self, pwd: &[u8], salt: &[u8], out: &[u8]) -> Result<(), Argon2Error> {
+        if out.len() < 4 {
+            return Err(Argon2Error::Output);
+        }
+        if salt.len() < 8 {
+            return Err(Argon2Error::Salt);
+        }
+        if [pwd, salt, self.k, self.x, out].iter().any(|d| d.len() > u32::MAX as usize) {
+            return Err(Argon2Error::Input);
+        }
+        if !(1..=0xff_ffff).contains(&self.p) {
+            return Err(Argon2Error::Lanes);
+        }
+        if self.m < 8 * self.p {
+            return Err(Argon2Error::Memory);
+        }
+        if self.t == 0 {
+            return Err(Argon2Error::Time);
+        }
+        Ok(())
+    }
+
+    /// Hash a password into out, the tag of at least 4 bytes, using the given memory of at least memory_blocks() blocks.
+    pub fn hash_with_memory(
+        &self,
+        pwd: &[u8],
+        salt: &[u8],
+        out: &mut [u8],
+        memory: &mut [Argon2Block],
+    ) -> Result<(), Argon2Error> {
+        self.check(pwd, salt, out)?;
+        let n = self.memory_blocks();
+        if memory.len() < n {
+            return Err(Argon2Error::Blocks);
+        }
+        let memory = &mut memory[..n];
+        let q = n as u32 / self.p;
+
+        let mut p = blake2b_params();
+        p.digest(64);
+        let mut h = blake2b(p);
+        for v in [self.p, out.len() as u32, self.m, self.t, ARGON2_VERSION, self.y as u32] {
+            h.update(&v.to_le_bytes());
+        }
+        for d in [pwd, salt, self.k, self.x] {
+            h.update(&(d.len() as u32).to_le_bytes());
+            h.update(d);
+        }
+        let mut h0 = [0; 64];
+        h.digest(&mut h0);
+        let mut b = [0; ARGON2_BB];
+        for (l, lane) in memory.chunks_exact_mut(q as usize).enumerate() {
+            for (i, e) in lane[..2].iter_mut().enumerate() {
+                blake2b_long(&mut b, &[&h0, &(i as u32).to_le_bytes(), &(l as u32).to_le_bytes()]);
+                *e = Argon2Block::from_bytes(&b);
+            }
+        }
+
+        for pass in 0..self.t {
+            for slice in 0..ARGON2_SYNC_POINTS {
+                #[cfg(feature = "std")]
+                if self.threads > 1 && self.p > 1 {
+                    self.fill_slice_threaded(memory, q, pass, slice);
+                    continue;
+                }
+                let mut w = Whole { m: memory, q };
+                for lane in 0..self.p {
+                    self.fill_segment(&mut w, q, pass, slice, lane);
+                }
+            }
+        }
+
+        let mut c = memory[q as usize - 1].clone();
+        for lane in memory.chunks_exact(q as usize).skip(1) {
+            c.0.iter_mut().zip(&lane[q as usize - 1].0).for_each(|(c, x)| *c ^= x);
+        }
+        blake2b_long(out, &[&c.to_bytes()]);
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    fn fill_slice_threaded(&self, memory: &mut [Argon2Block], q: u32, pass: u32, slice: u32) {
+        let sl = (q / ARGON2_SYNC_POINTS) as usize;
+        let start = slice as usize * sl;
+        let mut around = std::vec::Vec::with_capacity(self.p as usize);
+        let mut segs = std::vec::Vec::with_capacity(self.p as usize);
+        for lane in memory.chunks_exact_mut(q as usize) {
+            let (a, rest) = lane.split_at_mut(start);
+            let (seg, b) = rest.split_at_mut(sl);
+            around.push((&*a, &*b));
+            segs.push(seg);
+        }
+        let around = &around[..];
+        let k = self.p.div_ceil(self.threads) as usize;
+        std::thread::scope(|s| {
+            for (i, chunk) in segs.chunks_mut(k).enumerate() {
+                s.spawn(move || {
+                    for (j, seg) in chunk.iter_mut().enumerate() {
+                        let mut w = Split { around, seg, start: start as u32 };
+                        self.fill_segment(&mut w, q, pass, slice, (i * k + j) as u32);
+                    }
+                });
+            }
+        });
+    }
+
+    fn fill_segment(&self, w: &mut impl Lanes, q: u32, pass: u32, slice: u32, lane: u32) {
+        let sl = q / ARGON2_SYNC_POINTS;
+        let independent = self.y == Argon2Type::Argon2i || (self.y == Argon2Type::Argon2id && pass == 0 && slice < 2);
+        let zero = Argon2Block::default();
+        let mut input = Argon2Block::default();
+        let mut addresses = Argon2Block::default();
+        if independent {
+            input.0[..6].copy_from_slice(&[pass, lane, slice, q * self.p, self.t, self.y as u32].map(u64::from));
+        }
+        let next_addresses = |input: &mut Argon2Block, addresses: &mut Argon2Block| {
+            input.0[6] += 1;
+            *addresses = compress(&zero, &compress(&zero, input));
+        };
+        let first = if pass == 0 && slice == 0 { 2 } else { 0 };
+        if independent && first != 0 {
+            next_addresses(&mut input, &mut addresses);
+        }
+        for i in first..sl {
+            let col = slice * sl + i;
+            let prev = if col == 0 { q - 1 } else { col - 1 };
+            let rand = if independent {
+                if i % ARGON2_QQ as u32 == 0 {
+                    next_addresses(&mut input, &mut addresses);
+                }
+                addresses.0[i as usize % ARGON2_QQ]
+            } else {
+                w.block(lane, prev).0[0]
+            };
+            let ref_lane = if pass == 0 && slice == 0 { lane } else { ((rand >> 32) % self.p as u64) as u32 };
+            let same = ref_lane == lane;
+            let area = if pass == 0 {
+                if slice == 0 || same {
+                    slice * sl + i - 1
+                } else {
+                    slice * sl - (i == 0) as u32
+                }
+            } else if same {
+                q - sl + i - 1
+            } else {
+                q - sl - (i == 0) as u32
+            };
+            let x = rand & 0xffff_ffff;
+            let x = (x * x) >> 32;
+            let rel = area as u64 - 1 - ((area as u64 * x) >> 32);
+            let start = if pass != 0 && slice != ARGON2_SYNC_POINTS - 1 { (slice + 1) * sl } else { 0 };
+            let ref_col = ((start as u64 + rel) % q as u64) as u32;
+            let b = compress(w.block(lane, prev), w.block(ref_lane, ref_col));
+            let c = w.block_mut(lane, col);
+            if pass == 0 {
+                *c = b;
+            } else {
+                c.0.iter_mut().zip(b.0).for_each(|(c, b)| *c ^= b);
+            }
+        }
+    }
+
+    /// Hash a password into out, the tag of at least 4 bytes.
+    #[cfg(feature = "std")]
+    pub fn hash(&self, pwd: &[u8], salt: &[u8], out: &mut [u8]) -> Result<(), Argon2Error> {
+        self.check(pwd, salt, out)?;
+        let mut memory = std::vec![Argon2Block::default(); self.memory_blocks()];
+        self.hash_with_memory(pwd, salt, out, &mut memory)
+    }
+
+    /// Hash a password to a PHC string with a tag of the given length, such as
+    /// "$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$...". The secret and associated data are not part of the string.
+    #[cfg(feature = "std")]
+    pub fn hash_encoded(&self, pwd: &[u8], salt: &[u8], n: usize) -> Result<std::string::String, Argon2Error> {
+        let mut out = std::vec![0; n];
+        self.hash(pwd, salt, &mut out)?;
+        Ok(std::format!(
+            "${}$v={}$m={},t={},p={}${}${}",
+            self.y.name(),
+            ARGON2_VERSION,
+            self.m,
+            self.t,
+            self.p,
+            b64_encode(salt),
+            b64_encode(&out)
+        ))
+    }
+
+    /// Verify a password against a PHC string. The variant and costs come from the string, the secret, associated
+    /// data and threads from self.
+    #[cfg(feature = "std")]
+    pub fn verify(&self, encoded: &str, pwd: &[u8]) -> Result<bool, Argon2Error> {
+        let e = Argon2Error::Encoding;
+        let mut it = encoded.split('$');
+        if it.next() != Some("") {
+            return Err(e);
+        }
+        let mut a = self.clone();
+        a.y = it.next().and_then(Argon2Type::from_name).ok_or(e)?;
+        if it.next() != Some("v=19") {
+            return Err(e);
+        }
+        let mut costs = it.next().ok_or(e)?.split(',');
+        for (k, v) in [("m=", &mut a.m), ("t=", &mut a.t), ("p=", &mut a.p)] {
+            let s = costs.next().and_then(|s| s.strip_prefix(k)).ok_or(e)?;
+            if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0')) {
+                return Err(e);
+            }
+            *v = s.parse().map_err(|_| e)?;
+        }
+        let salt = it.next().and_then(b64_decode).ok_or(e)?;
+        let tag = it.next().and_then(b64_decode).ok_or(e)?;
+        if costs.next().is_some() || it.next().is_some() {
+            return Err(e);
+        }
+        let mut out = std::vec![0; tag.len()];
+        a.hash(pwd, &salt, &mut out)?;
+        Ok(out.iter().zip(&tag).fold(0, |r, (a, b)| r | (a ^ b)) == 0)
+    }
+}
+
+/// Create the parameters of an Argon2 variant, with the minimum costs recommended by OWASP: 19 MiB of memory, two
+/// passes and one lane.
+pub fn argon2(y: Argon2Type) -> Argon2<'static> {
+    Argon2 {
+        y,
+        m: 19456,
+        t: 2,
+        p: 1,
+        k: &[],
+        x: &[],
+        #[cfg(feature = "std")]
+        threads: 1,
+    }
+}
+
+#[cfg(feature = "std")]
+const B64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Base64 without padding, as in PHC strings.
+#[cfg(feature = "std")]
+fn b64_encode(d: &[u8]) -> std::string::String {
+    let mut r = std::string::String::new();
+    for c in d.chunks(3) {
+        let n = c.iter().enumerate().fold(0, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..=c.len() {
+            r.push(B64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    r
+}
+
+/// Decode base64 without padding. Trailing bits must be zero.
+#[cfg(feature = "std")]
+fn b64_decode(s: &str) -> Option<std::vec::Vec<u8>> {
+    let mut r = std::vec::Vec::new();
+    for c in s.as_bytes().chunks(4) {
+        if c.len() == 1 {
+            return None;
+        }
+        let mut n = 0;
+        for (i, &b) in c.iter().enumerate() {
+            n |= (B64.iter().position(|&x| x == b)? as u32) << (18 - 6 * i);
+        }
+        let k = c.len() - 1;
+        if n & ((1 << (24 - 8 * k)) - 1) != 0 {
+            return None;
+        }
+        r.extend_from_slice(&n.to_be_bytes()[1..1 + k]);
+    }
+    Some(r)
+}
@@ -0,0 +1,413 @@
+//! Print BLAKE2 checksums. Output and options follow b2sum from GNU coreutils and the BLAKE2 reference implementation.
+
+use blake2ya::{Algorithm, SumParams};
+use std::ffi::OsString;
+use std::io::Write;
+use std::process::ExitCode;
+
+const HELP: &str = "\
+Usage: b2sum-ya [OPTION]... [FILE]...
+Print BLAKE2 checksums.
+
+With no FILE, or when FILE is -, read standard input.
+
+  -a, --algorithm=ALGO  hash algorithm: blake2b (default), blake2s, blake2bp
+                          or blake2sp
+  -b, --binary          read in binary mode
+  -c, --check           read checksums from the FILEs and check them
+  -l, --length=BITS     digest length in bits; must not exceed the max for
+                          the blake2 algorithm and must be a multiple of 8
+      --tag             create a BSD-style checksum
+  -t, --text            read in text mode (default)
+      --tree            print one BLAKE2b digest over each FILE as a directory
+                          tree, with names, types, sizes, the executable bit
+                          and symlink targets
+  -z, --zero            end each output line with NUL, not newline,
+                          and disable file name escaping
+
+Keyed hashing and variable-length output:
+      --key-file=FILE   read the key from the raw bytes of FILE
+      --key-env=VAR     read the key in hex from environment variable VAR
+      --salt=HEX        salt, of up to 16 bytes for blake2b and blake2bp,
+                          8 bytes for blake2s and blake2sp
+      --person=HEX      personalization, with the same limits as the salt
+      --xof=BYTES       BLAKE2X output of BYTES bytes, with blake2b or blake2s
+      --raw             write the digests as raw bytes, without file names
+
+The following four options are useful only when verifying checksums:
+      --ignore-missing  don't fail or report status for missing files
+      --quiet           don't print OK for each successfully verified file
+      --status          don't output anything, status code shows success
+      --strict          exit non-zero for improperly formatted checksum lines
+
+      --help            display this help and exit
+      --version         output version information and exit
+";
+
+/// Command line options.
+struct Args {
+    algorithm: Algorithm,
+    /// Digest length in bits, 0 for the maximum of the algorithm.
+    length: usize,
+    binary: bool,
+    tag: bool,
+    zero: bool,
+    check: bool,
+    ignore_missing: bool,
+    quiet: bool,
+    status: bool,
+    strict: bool,
+    tree: bool,
+    params: SumParams,
+    /// BLAKE2X output byte length, 0 for a regular digest.
+    xof: usize,
+    raw: bool,
+    files: Vec<OsString>,
+}
+
+/// Outcome of parsing the command line that ends the program early.
+enum Exit {
+    Help,
+    Version,
+    Usage(String),
+    /// An error not caused by the syntax of the command line.
+    Error(String),
+}
+
+/// Decode a hex string, as given for keys, salts and personalizations.
+fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok()).collect()
+}
+
+fn parse(argv: Vec<OsString>) -> Result<Args, Exit> {
+    let mut a = Args {
+        algorithm: Algorithm::Blake2b,
+        length: 0,
+        binary: false,
+        tag: false,
+        zero: false,
+        check: false,
+        ignore_missing: false,
+        quiet: false,
+        status: false,
+        strict: false,
+        tree: false,
+        params: SumParams::default(),
+        xof: 0,
+        raw: false,
+        files: vec![],
+    };
+    let mut algorithm = None;
+    let mut length = None;
+    let mut key_file = None;
+    let mut key_env = None;
+    let mut salt = None;
+    let mut person = None;
+    let mut xof = None;
+    let mut i = argv.into_iter();
+    while let Some(arg) = i.next() {
+        let s = match arg.to_str() {
+            Some(s) if s.starts_with('-') && s != "-" => s.to_string(),
+            _ => {
+                a.files.push(arg);
+                continue;
+            }
+        };
+        if s == "--" {
+            a.files.extend(i);
+            break;
+        }
+        if let Some(long) = s.strip_prefix("--") {
+            let (k, v) = match long.split_once('=') {
+                Some((k, v)) => (k, Some(v.to_string())),
+                None => (long, None),
+            };
+            let mut value = |v: Option<String>| match v {
+                Some(v) => Ok(v),
+                None => i
+                    .next()
+                    .and_then(|v| v.into_string().ok())
+                    .ok_or_else(|| Exit::Usage(format!("option '--{}' requires an argument", k))),
+            };
+            match k {
+                "algorithm" => algorithm = Some(value(v)?),
+                "length" => length = Some(value(v)?),
+                "binary" => a.binary = true,
+                "check" => a.check = true,
+                "ignore-missing" => a.ignore_missing = true,
+                "quiet" => a.quiet = true,
+                "status" => a.status = true,
+                "strict" => a.strict = true,
+                "text" => a.binary = false,
+                "tree" => a.tree = true,
+                "tag" => a.tag = true,
+                "key-file" => key_file = Some(value(v)?),
+                "key-env" => key_env = Some(value(v)?),
+                "salt" => salt = Some(value(v)?),
+                "person" => person = Some(value(v)?),
+                "xof" => xof = Some(value(v)?),
+                "raw" => a.raw = true,
+                "zero" => a.zero = true,
+                "help" => return Err(Exit::Help),
+                "version" => return Err(Exit::Version),
+                _ => return Err(Exit::Usage(format!("unrecognized option '{}'", s))),
+            }
+            continue;
+        }
+        for (j, o) in s[1..].char_indices() {
+            match o {
+                'a' | 'l' => {
+                    let rest = &s[1 + j + 1..];
+                    let v = if !rest.is_empty() {
+                        rest.to_string()
+                    } else {
+                        i.next()
+                            .and_then(|v| v.into_string().ok())
+                            .ok_or_else(|| Exit::Usage(format!("option requires an argument -- '{}'", o)))?
+                    };
+                    if o == 'a' {
+                        algorithm = Some(v);
+                    } else {
+                        length = Some(v);
+                    }
+                    break;
+                }
+                'b' => a.binary = true,
+                'c' => a.check = true,
+                't' => a.binary = false,
+                'z' => a.zero = true,
+                _ => return Err(Exit::Usage(format!("invalid option -- '{}'", o))),
+            }
+        }
+    }
+    if let Some(v) = algorithm {
+        a.algorithm = Algorithm::from_name(&v).ok_or_else(|| Exit::Usage(format!("invalid algorithm: '{}'", v)))?;
+    }
+    if let Some(v) = length {
+        a.length = match v.parse::<usize>() {
+            Ok(n) if n <= a.algorithm.size() * 8 => n,
+            _ => return Err(Exit::Usage(format!("invalid length: '{}'", v))),
+        };
+        if !a.length.is_multiple_of(8) {
+            return Err(Exit::Usage("length is not a multiple of 8".to_string()));
+        }
+    }
+    for (v, name, to) in [(salt, "salt", &mut a.params.salt), (person, "personalization", &mut a.params.person)] {
+        if let Some(v) = v {
+            *to = unhex(&v).ok_or_else(|| Exit::Usage(format!("invalid {}: '{}'", name, v)))?;
+        }
+    }
+    match (key_file, key_env) {
+        (Some(_), Some(_)) => return Err(Exit::Usage("only one of --key-file and --key-env can be given".to_string())),
+        (Some(f), None) => {
+            a.params.key = std::fs::read(&f).map_err(|e| Exit::Error(format!("{}: {}", f, strerror(&e))))?;
+        }
+        (None, Some(v)) => {
+            let k = std::env::var(&v).map_err(|_| Exit::Error(format!("{}: environment variable not set", v)))?;
+            a.params.key = unhex(k.trim()).ok_or_else(|| Exit::Error(format!("{}: key is not hex", v)))?;
+        }
+        (None, None) => {}
+    }
+    if a.params.key.len() > a.algorithm.size() {
+        return Err(Exit::Error(format!("key is longer than {} bytes", a.algorithm.size())));
+    }
+    if !a.params.fits(a.algorithm) {
+        return Err(Exit::Usage(format!("salt and personalization are limited to {} bytes", a.algorithm.size() / 4)));
+    }
+    if let Some(v) = xof {
+        let max = match a.algorithm {
+            Algorithm::Blake2b => blake2ya::BLAKE2XB_UNKNOWN as usize - 1,
+            Algorithm::Blake2s => blake2ya::BLAKE2XS_UNKNOWN as usize - 1,
+            _ => return Err(Exit::Usage("the --xof option is only supported with blake2b and blake2s".to_string())),
+        };
+        a.xof = match v.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= max => n,
+            _ => return Err(Exit::Usage(format!("invalid output length: '{}'", v))),
+        };
+        a.params.xof = true;
+        if a.length != 0 || a.check || a.tag {
+            return Err(Exit::Usage("the --xof option cannot be combined with --length, --check or --tag".to_string()));
+        }
+    }
+    if a.raw && (a.check || a.tag || a.zero) {
+        return Err(Exit::Usage("the --raw option cannot be combined with --check, --tag or --zero".to_string()));
+    }
+    if a.tag && a.check {
+        return Err(Exit::Usage("the --tag option is meaningless when verifying checksums".to_string()));
+    }
+    if a.tree && (a.check || a.tag || a.algorithm != Algorithm::Blake2b || a.params != SumParams::default()) {
+        return Err(Exit::Usage("the --tree option is only supported with unkeyed blake2b digests".to_string()));
+    }
+    for (on, name) in
+        [(a.ignore_missing, "--ignore-missing"), (a.quiet, "--quiet"), (a.status, "--status"), (a.strict, "--strict")]
+    {
+        if on && !a.check {
+            return Err(Exit::Usage(format!("the {} option is meaningful only when verifying checksums", name)));
+        }
+    }
+    if a.files.is_empty() {
+        a.files.push(OsString::from("-"));
+    }
+    Ok(a)
+}
+
+/// Format one output line, including its terminator.
+fn line(a: &Args, name: &[u8], digest: &[u8]) -> Vec<u8> {
+    let mut r = Vec::new();
+    let escaped = if a.zero { None } else { blake2ya::escape(name) };
+    if escaped.is_some() {
+        r.push(b'\\');
+    }
+    let name = escaped.as_deref().unwrap_or(name);
+    let hex = digest.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    if a.tag {
+        r.extend_from_slice(a.algorithm.name().as_bytes());
+        if digest.len() != a.algorithm.size() {
+            r.extend_from_slice(format!("-{}", digest.len() * 8).as_bytes());
+        }
+        r.extend_from_slice(b" (");
+        r.extend_from_slice(name);
+        r.extend_from_slice(b") = ");
+        r.extend_from_slice(hex.as_bytes());
+    } else {
+        r.extend_from_slice(hex.as_bytes());
+        r.extend_from_slice(if a.binary { b" *" } else { b"  " });
+        r.extend_from_slice(name);
+    }
+    r.push(if a.zero { 0 } else { b'\n' });
+    r
+}
+
+/// Describe an I/O error without the OS error code, like the C library's strerror.
+fn strerror(e: &std::io::Error) -> String {
+    let s = e.to_string();
+    match s.find(" (os error") {
+        Some(i) => s[..i].to_string(),
+        None => s,
+    }
+}
+
+/// Print a warning about some lines of a manifest, in singular or plural.
+fn warning(prog: &str, n: usize, one: &str, many: &str) {
+    match n {
+        0 => {}
+        1 => eprintln!("{}: WARNING: 1 {}", prog, one),
+        n => eprintln!("{}: WARNING: {} {}", prog, n, many),
+    }
+}
+
+/// Verify manifests instead of computing checksums.
+fn check(prog: &str, a: &Args) -> ExitCode {
+    let mut c = blake2ya::check();
+    c.algorithm(a.algorithm);
+    c.params(a.params.clone());
+    c.ignore_missing(a.ignore_missing);
+    c.quiet(a.quiet);
+    c.status(a.status);
+    c.strict(a.strict);
+    let mut ok = true;
+    for f in &a.files {
+        let warn = |p: &std::path::Path, e: &std::io::Error| eprintln!("{}: {}: {}", prog, p.display(), strerror(e));
+        let r = if f == "-" {
+            c.run(std::io::stdin().lock(), std::io::stdout().lock(), warn)
+        } else {
+            std::fs::File::open(f).and_then(|r| c.run(std::io::BufReader::new(r), std::io::stdout().lock(), warn))
+        };
+        let s = match r {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("{}: {}: {}", prog, f.to_string_lossy(), strerror(&e));
+                ok = false;
+                continue;
+            }
+        };
+        if s.ok + s.failed + s.missing + s.ignored == 0 {
+            eprintln!("{}: {}: no properly formatted checksum lines found", prog, f.to_string_lossy());
+        } else if !a.status {
+            warning(prog, s.malformed, "line is improperly formatted", "lines are improperly formatted");
+            warning(prog, s.missing, "listed file could not be read", "listed files could not be read");
+            warning(prog, s.failed, "computed checksum did NOT match", "computed checksums did NOT match");
+        }
+        if a.ignore_missing && s.ok + s.failed + s.missing == 0 && s.ignored > 0 {
+            eprintln!("{}: {}: no file was verified", prog, f.to_string_lossy());
+        }
+        ok &= s.success();
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn main() -> ExitCode {
+    let mut argv = std::env::args_os();
+    let prog = argv
+        .next()
+        .and_then(|p| std::path::Path::new(&p).file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "b2sum-ya".to_string());
+    let a = match parse(argv.collect()) {
+        Ok(a) => a,
+        Err(Exit::Help) => {
+            print!("{}", HELP.replace("b2sum-ya", &prog));
+            return ExitCode::SUCCESS;
+        }
+        Err(Exit::Version) => {
+            println!("{} (blake2ya) {}", prog, env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(Exit::Usage(m)) => {
+            eprintln!("{}: {}", prog, m);
+            eprintln!("Try '{} --help' for more information.", prog);
+            return ExitCode::FAILURE;
+        }
+        Err(Exit::Error(m)) => {
+            eprintln!("{}: {}", prog, m);
+            return ExitCode::FAILURE;
+        }
+    };
+    if a.check {
+        return check(&prog, &a);
+    }
+    let n = match (a.xof, a.length) {
+        (0, 0) => a.algorithm.size(),
+        (0, l) => l / 8,
+        (x, _) => x,
+    };
+    let mut ok = true;
+    let mut stdout = std::io::stdout().lock();
+    for f in &a.files {
+        let r = if a.tree {
+            blake2ya::tree(std::path::Path::new(f), n)
+        } else if f == "-" {
+            blake2ya::sum_params(a.algorithm, n, &a.params, std::io::stdin().lock())
+        } else {
+            std::fs::File::open(f).and_then(|r| blake2ya::sum_params(a.algorithm, n, &a.params, r))
+        };
+        match r {
+            Ok(d) => {
+                let w =
+                    if a.raw { stdout.write_all(&d) } else { stdout.write_all(&line(&a, f.as_encoded_bytes(), &d)) };
+                if w.is_err() {
+                    return ExitCode::FAILURE;
+                }
+            }
+            Err(e) => {
+                let _ = stdout.flush();
+                if a.tree {
+                    eprintln!("{}: {}", prog, strerror(&e));
+                } else {
+                    eprintln!("{}: {}: {}", prog, f.to_string_lossy(), strerror(&e));
+                }
+                ok = false;
+            }
+        }
+    }
+    if stdout.flush().is_err() || !ok {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
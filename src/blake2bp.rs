@@ -0,0 +1,85 @@
+//! BLAKE2bp, the 4-way parallel mode of BLAKE2b. The input is split into blocks which are dealt to 4 leaves in turn,
+//! and the root hashes the concatenated leaf outputs.
+
+use crate::blake2b::{blake2b, blake2b_unkeyed, Blake2b, Param2b};
+
+/// Degree of parallelism.
+const BLAKE2BP_PP: usize = 4;
+/// Block bytes.
+const BLAKE2BP_BB: usize = 128;
+/// Hash bytes.
+const BLAKE2BP_NN: u8 = 64;
+
+/// A context for computing the BLAKE2bp checksum.
+#[derive(Clone)]
+pub struct Blake2bp {
+    /// Leaves, the i-th one hashes blocks i, i + 4, i + 8, ... of the input.
+    s: [Blake2b; BLAKE2BP_PP],
+    /// Root.
+    r: Blake2b,
+    /// Message byte offset modulo the length of a round of blocks over all leaves.
+    o: usize,
+}
+
+impl Blake2bp {
+    /// Update this hash object's state with the provided data.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            let n = (BLAKE2BP_BB - self.o % BLAKE2BP_BB).min(data.len());
+            self.s[self.o / BLAKE2BP_BB].update(&data[..n]);
+            self.o = (self.o + n) % (BLAKE2BP_PP * BLAKE2BP_BB);
+            data = &data[n..];
+        }
+    }
+
+    /// Update this hash object's state with the provided data, hashing the leaves on the threads of the current rayon
+    /// pool.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn update_rayon(&mut self, data: &[u8]) {
+        use rayon::prelude::*;
+        let a = ((BLAKE2BP_PP * BLAKE2BP_BB - self.o) % (BLAKE2BP_PP * BLAKE2BP_BB)).min(data.len());
+        self.update(&data[..a]);
+        let data = &data[a..];
+        let m = data.len() - data.len() % (BLAKE2BP_PP * BLAKE2BP_BB);
+        self.s.par_iter_mut().enumerate().for_each(|(i, s)| {
+            for b in data[..m].chunks(BLAKE2BP_BB).skip(i).step_by(BLAKE2BP_PP) {
+                s.update(b);
+            }
+        });
+        self.update(&data[m..]);
+    }
+
+    /// Return the digest value.
+    pub fn digest(&mut self, d: &mut [u8]) {
+        for s in &mut self.s {
+            self.r.update(&s.finalize());
+        }
+        self.r.digest(d);
+    }
+}
+
+/// Core hasher state of BLAKE2bp. The digest length, key, salt and personalization are taken from the parameter block,
+/// tree parameters are set by this function.
+pub fn blake2bp(param2b: Param2b) -> Blake2bp {
+    let mut p = param2b;
+    p.fanout(BLAKE2BP_PP as u8);
+    p.depth(2);
+    p.leaf_length(0);
+    p.inner_length(BLAKE2BP_NN);
+    let s = core::array::from_fn(|i| {
+        let mut p = p.clone();
+        p.node_offset(i as u64);
+        p.node_depth(0);
+        let mut s = blake2b(p);
+        if i == BLAKE2BP_PP - 1 {
+            s.last_node();
+        }
+        s
+    });
+    p.node_offset(0);
+    p.node_depth(1);
+    let mut r = blake2b_unkeyed(&p);
+    r.last_node();
+    Blake2bp { s, r, o: 0 }
+}
@@ -59,6 +59,18 @@ fn interp_bb2w(b: &[u8; BLAKE2S_BB]) -> [u32; BLAKE2S_BB / 4] {
     w
 }
 
+/// Interpretation of bytes as words, starting at offset "o" and padding with zeros past the end of the data. Usable in
+/// const contexts.
+const fn interp_cb2w(b: &[u8], o: usize) -> [u32; BLAKE2S_BB / 4] {
+    let mut w = [0; BLAKE2S_BB / 4];
+    let mut i = 0;
+    while i < BLAKE2S_BB && o + i < b.len() {
+        w[i / 4] |= (b[o + i] as u32) << (8 * (i % 4));
+        i += 1;
+    }
+    w
+}
+
 /// The G primitive function mixes two input words, "x" and "y", into four words indexed by "a", "b", "c", and "d" in
 /// the working vector v[0..15].
 fn mixing(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
@@ -72,11 +84,146 @@ fn mixing(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y:
     v[b] = (v[b] ^ v[c]).rotate_right(BLAKE2S_R4);
 }
 
+/// Apply the 10 rounds of F to the working vector. Improve performance by using loop unrolling. This can give us about
+/// 100% speedup.
+#[cfg(not(feature = "small"))]
+fn rounds(v: &mut [u32; 16], m: &[u32; 16]) {
+    let s = BLAKE2S_SIGMA[0x0];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x1];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x2];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x3];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x4];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x5];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x6];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x7];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x8];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    let s = BLAKE2S_SIGMA[0x9];
+    mixing(v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
+    mixing(v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
+    mixing(v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
+    mixing(v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
+    mixing(v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
+    mixing(v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
+    mixing(v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
+    mixing(v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+}
+
+/// Indices of the working vector mixed by each G function in a round: four columns followed by four diagonals.
+const BLAKE2S_G: [[usize; 4]; 8] = [
+    [0x0, 0x4, 0x8, 0xc],
+    [0x1, 0x5, 0x9, 0xd],
+    [0x2, 0x6, 0xa, 0xe],
+    [0x3, 0x7, 0xb, 0xf],
+    [0x0, 0x5, 0xa, 0xf],
+    [0x1, 0x6, 0xb, 0xc],
+    [0x2, 0x7, 0x8, 0xd],
+    [0x3, 0x4, 0x9, 0xe],
+];
+
+/// Apply the 10 rounds of F to the working vector. Loops over the rows of SIGMA instead of unrolling them, trading
+/// speed for code size.
+#[cfg(feature = "small")]
+fn rounds(v: &mut [u32; 16], m: &[u32; 16]) {
+    rounds_n(v, m, 10);
+}
+
+/// Apply n rounds of F to the working vector, starting over at the first row of SIGMA every 10 rounds.
+fn rounds_n(v: &mut [u32; 16], m: &[u32; 16], n: u32) {
+    let mut r = 0;
+    while r < n {
+        let s = BLAKE2S_SIGMA[(r % 10) as usize];
+        let mut i = 0;
+        while i < BLAKE2S_G.len() {
+            let g = BLAKE2S_G[i];
+            mixing(v, g[0], g[1], g[2], g[3], m[s[i * 2] as usize], m[s[i * 2 + 1] as usize]);
+            i += 1;
+        }
+        r += 1;
+    }
+}
+
 /// Compression function F takes as an argument the state vector "h", message block vector "m" (last block is padded
 /// with zeros to full block size, if required), 2w-bit offset counter "t", and final block indicator flag "f".  Local
 /// vector v[0..15] is used in processing. F returns a new state vector. The number of rounds, "r", is 12 for BLAKE2b
 /// and 10 for BLAKE2s. Rounds are numbered from 0 to r - 1.
 fn reduce(h: &mut [u32; 8], m: &[u32; 16], t: &[u32; 2], f: &[u32; 2]) {
+    reduce_rounds(h, m, t, f, 10);
+}
+
+/// F with n rounds. The standard 10 rounds are unrolled unless the small feature is enabled.
+fn reduce_rounds(h: &mut [u32; 8], m: &[u32; 16], t: &[u32; 2], f: &[u32; 2], n: u32) {
     let mut v = [0x00; 16];
     v[0x00..0x08].copy_from_slice(h);
     v[0x08..0x10].copy_from_slice(&BLAKE2S_IV);
@@ -84,97 +231,11 @@ fn reduce(h: &mut [u32; 8], m: &[u32; 16], t: &[u32; 2], f: &[u32; 2]) {
     v[0x0d] ^= t[1];
     v[0x0e] ^= f[0];
     v[0x0f] ^= f[1];
-    // Improve performance by using loop unrolling. This can give us about 100% speedup.
-    let s = BLAKE2S_SIGMA[0x0];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x1];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x2];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x3];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x4];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x5];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x6];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x7];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x8];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
-    let s = BLAKE2S_SIGMA[0x9];
-    mixing(&mut v, 0x0, 0x4, 0x8, 0xc, m[s[0x0] as usize], m[s[0x1] as usize]);
-    mixing(&mut v, 0x1, 0x5, 0x9, 0xd, m[s[0x2] as usize], m[s[0x3] as usize]);
-    mixing(&mut v, 0x2, 0x6, 0xa, 0xe, m[s[0x4] as usize], m[s[0x5] as usize]);
-    mixing(&mut v, 0x3, 0x7, 0xb, 0xf, m[s[0x6] as usize], m[s[0x7] as usize]);
-    mixing(&mut v, 0x0, 0x5, 0xa, 0xf, m[s[0x8] as usize], m[s[0x9] as usize]);
-    mixing(&mut v, 0x1, 0x6, 0xb, 0xc, m[s[0xa] as usize], m[s[0xb] as usize]);
-    mixing(&mut v, 0x2, 0x7, 0x8, 0xd, m[s[0xc] as usize], m[s[0xd] as usize]);
-    mixing(&mut v, 0x3, 0x4, 0x9, 0xe, m[s[0xe] as usize], m[s[0xf] as usize]);
+    if n == 10 {
+        rounds(&mut v, m);
+    } else {
+        rounds_n(&mut v, m, n);
+    }
     h[0x0] = h[0x0] ^ v[0x0] ^ v[0x8];
     h[0x1] = h[0x1] ^ v[0x1] ^ v[0x9];
     h[0x2] = h[0x2] ^ v[0x2] ^ v[0xa];
@@ -191,7 +252,61 @@ fn incoff(t: &mut [u32; 2], n: u32) {
     t[1] = t[1].wrapping_add((t[0] < n) as u32);
 }
 
+/// G of blake2s_const(), taking and returning the working vector by value so that it can be evaluated in a const
+/// context.
+const fn mixing_const(mut v: [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) -> [u32; 16] {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(BLAKE2S_R1);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(BLAKE2S_R2);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(BLAKE2S_R3);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(BLAKE2S_R4);
+    v
+}
+
+/// F of blake2s_const(), looping over the rows of SIGMA. Takes the state vector and returns the new one.
+const fn reduce_const(h: [u32; 8], m: &[u32; 16], t: [u32; 2], f: [u32; 2]) -> [u32; 8] {
+    let mut v = [0x00; 16];
+    let mut i = 0;
+    while i < 8 {
+        v[i] = h[i];
+        v[i + 8] = BLAKE2S_IV[i];
+        i += 1;
+    }
+    v[0x0c] ^= t[0];
+    v[0x0d] ^= t[1];
+    v[0x0e] ^= f[0];
+    v[0x0f] ^= f[1];
+    let mut r = 0;
+    while r < BLAKE2S_SIGMA.len() {
+        let s = BLAKE2S_SIGMA[r];
+        let mut i = 0;
+        while i < BLAKE2S_G.len() {
+            let g = BLAKE2S_G[i];
+            v = mixing_const(v, g[0], g[1], g[2], g[3], m[s[i * 2] as usize], m[s[i * 2 + 1] as usize]);
+            i += 1;
+        }
+        r += 1;
+    }
+    let mut r = h;
+    let mut i = 0;
+    while i < 8 {
+        r[i] ^= v[i] ^ v[i + 8];
+        i += 1;
+    }
+    r
+}
+
+/// Add n to message byte offset of blake2s_const().
+const fn incoff_const(t: [u32; 2], n: u32) -> [u32; 2] {
+    let t0 = t[0].wrapping_add(n);
+    [t0, t[1].wrapping_add((t0 < n) as u32)]
+}
+
 /// BLAKE2s parameter block structure.
+#[derive(Clone)]
 pub struct Param2s {
     buf: [u8; 32],
     key: [u8; 32],
@@ -200,7 +315,7 @@ pub struct Param2s {
 impl Param2s {
     /// Set digest byte length. An integer in [1, 64] for BLAKE2b, in [1, 32] for BLAKE2s.
     pub fn digest(&mut self, n: u8) {
-        assert!(1 <= n && n <= 32);
+        assert!((1..=32).contains(&n));
         self.buf[0x00] = n;
     }
 
@@ -211,6 +326,40 @@ impl Param2s {
         self.key[..n.len()].copy_from_slice(n);
     }
 
+    /// Set fanout. An integer in [0, 255], 0 for unlimited and 1 for sequential mode.
+    pub fn fanout(&mut self, n: u8) {
+        self.buf[0x02] = n;
+    }
+
+    /// Set maximal depth. An integer in [1, 255], 255 for unlimited and 1 for sequential mode.
+    pub fn depth(&mut self, n: u8) {
+        assert!(n >= 1);
+        self.buf[0x03] = n;
+    }
+
+    /// Set leaf maximal byte length. 0 for unlimited or sequential mode.
+    pub fn leaf_length(&mut self, n: u32) {
+        self.buf[0x04..0x08].copy_from_slice(&n.to_le_bytes());
+    }
+
+    /// Set node offset. An integer in [0, 2^64) for BLAKE2b, in [0, 2^48) for BLAKE2s. 0 for the first, leftmost,
+    /// leaf, or in sequential mode.
+    pub fn node_offset(&mut self, n: u64) {
+        assert!(n < 1 << 48);
+        self.buf[0x08..0x08 + 6].copy_from_slice(&n.to_le_bytes()[..6]);
+    }
+
+    /// Set node depth. 0 for the leaves, or in sequential mode.
+    pub fn node_depth(&mut self, n: u8) {
+        self.buf[0x0e] = n;
+    }
+
+    /// Set inner hash byte length. An integer in [0, 64] for BLAKE2b, in [0, 32] for BLAKE2s. 0 in sequential mode.
+    pub fn inner_length(&mut self, n: u8) {
+        assert!(n <= 32);
+        self.buf[0x0f] = n;
+    }
+
     /// Set salt. An arbitrary string of 16 bytes for BLAKE2b, and 8 bytes for BLAKE2s.
     pub fn salt(&mut self, n: &[u8]) {
         assert!(n.len() <= 8);
@@ -222,9 +371,28 @@ impl Param2s {
         assert!(n.len() <= 8);
         self.buf[0x18..0x18 + n.len()].copy_from_slice(n);
     }
+
+    /// Set the BLAKE2X output byte length, stored in the upper part of the node offset.
+    pub(crate) fn xof_length(&mut self, n: u16) {
+        self.buf[0x0c..0x0e].copy_from_slice(&n.to_le_bytes());
+    }
+
+    /// Turn the parameters of the BLAKE2X input node into those of its i-th output node, of digest byte length n.
+    /// Salt, personalization and XOF length are kept.
+    pub(crate) fn xof_node(&mut self, i: u32, n: u8) {
+        self.buf[0x00] = n;
+        self.buf[0x01] = 0;
+        self.buf[0x02] = 0;
+        self.buf[0x03] = 0;
+        self.buf[0x04..0x08].copy_from_slice(&32u32.to_le_bytes());
+        self.buf[0x08..0x0c].copy_from_slice(&i.to_le_bytes());
+        self.buf[0x0e] = 0;
+        self.buf[0x0f] = 32;
+    }
 }
 
 /// A context for computing the BLAKE2s checksum.
+#[derive(Clone)]
 pub struct Blake2s {
     /// Internal state of the hash.
     h: [u32; 8],
@@ -236,8 +404,10 @@ pub struct Blake2s {
     b: [u8; BLAKE2S_BB],
     /// Buffer length.
     l: usize,
-    /// Parameter block.
-    p: Param2s,
+    /// Digest byte length.
+    n: u8,
+    /// Flag indicating the last node of a tree level, applied with the last block.
+    g: bool,
 }
 
 impl Blake2s {
@@ -268,14 +438,28 @@ impl Blake2s {
         self.l = dlen;
     }
 
-    /// Return the digest value.
-    pub fn digest(&mut self, d: &mut [u8]) {
+    /// Mark this hash object as the last node of its level in a tree. Not used in sequential mode.
+    pub fn last_node(&mut self) {
+        self.g = true;
+    }
+
+    /// Process the last block and return the whole chaining value, ignoring the digest byte length. Tree modes feed
+    /// this untruncated output to parent nodes.
+    pub(crate) fn finalize(&mut self) -> [u8; BLAKE2S_NN] {
         self.b[self.l..].fill(0);
         self.f[0] = u32::MAX;
+        if self.g {
+            self.f[1] = u32::MAX;
+        }
         incoff(&mut self.t, self.l as u32);
         reduce(&mut self.h, &interp_bb2w(&self.b), &self.t, &self.f);
-        let br = interp_hw2b(&self.h);
-        d.copy_from_slice(&br[..self.p.buf[0] as usize]);
+        interp_hw2b(&self.h)
+    }
+
+    /// Return the digest value.
+    pub fn digest(&mut self, d: &mut [u8]) {
+        let br = self.finalize();
+        d.copy_from_slice(&br[..self.n as usize]);
     }
 }
 
@@ -287,10 +471,11 @@ pub fn blake2s_params() -> Param2s {
     r
 }
 
-/// Core hasher state of BLAKE2s.
-pub fn blake2s(param2s: Param2s) -> Blake2s {
-    let mut r = Blake2s { h: [0; 8], t: [0; 2], f: [0; 2], b: [0; 64], l: 0, p: param2s };
-    let w = interp_hb2w(&r.p.buf);
+/// Hasher state of BLAKE2s initialized from the parameter block alone, without absorbing the key block.
+/// The root node of a parallel mode records the key length but does not process the key.
+pub(crate) fn blake2s_unkeyed(param2s: &Param2s) -> Blake2s {
+    let mut r = Blake2s { h: [0; 8], t: [0; 2], f: [0; 2], b: [0; 64], l: 0, n: param2s.buf[0], g: false };
+    let w = interp_hb2w(&param2s.buf);
     r.h[0x0] ^= BLAKE2S_IV[0x0] ^ w[0x0];
     r.h[0x1] ^= BLAKE2S_IV[0x1] ^ w[0x1];
     r.h[0x2] ^= BLAKE2S_IV[0x2] ^ w[0x2];
@@ -299,10 +484,47 @@ pub fn blake2s(param2s: Param2s) -> Blake2s {
     r.h[0x5] ^= BLAKE2S_IV[0x5] ^ w[0x5];
     r.h[0x6] ^= BLAKE2S_IV[0x6] ^ w[0x6];
     r.h[0x7] ^= BLAKE2S_IV[0x7] ^ w[0x7];
-    if r.p.buf[1] != 0 {
+    r
+}
+
+/// Core hasher state of BLAKE2s.
+pub fn blake2s(param2s: Param2s) -> Blake2s {
+    let mut r = blake2s_unkeyed(&param2s);
+    if param2s.buf[1] != 0 {
         let mut b = [0; BLAKE2S_BB];
-        b[..r.p.key.len()].copy_from_slice(&r.p.key);
+        b[..param2s.key.len()].copy_from_slice(&param2s.key);
         r.update(&b);
     }
     r
 }
+
+/// Compute the BLAKE2s digest of data with the default parameters in a const context. The digest length N is an integer
+/// in [1, 32]. It produces the same digest as blake2s(), but is slower at runtime.
+pub const fn blake2s_const<const N: usize>(data: &[u8]) -> [u8; N] {
+    assert!(N != 0 && N <= BLAKE2S_NN);
+    let mut h = BLAKE2S_IV;
+    h[0x0] ^= 0x01010000 ^ N as u32;
+    let mut t = [0; 2];
+    let mut doff = 0;
+    while data.len() - doff > BLAKE2S_BB {
+        t = incoff_const(t, BLAKE2S_BB as u32);
+        h = reduce_const(h, &interp_cb2w(data, doff), t, [0; 2]);
+        doff += BLAKE2S_BB;
+    }
+    t = incoff_const(t, (data.len() - doff) as u32);
+    h = reduce_const(h, &interp_cb2w(data, doff), t, [u32::MAX, 0]);
+    let mut r = [0; N];
+    let mut i = 0;
+    while i < N {
+        r[i] = (h[i / 4] >> (8 * (i % 4))) as u8;
+        i += 1;
+    }
+    r
+}
+
+/// Compression function F of BLAKE2s with the given number of rounds, for custom modes built on F. Past 10 rounds, the
+/// message schedule starts over at the first row of SIGMA. h is updated in place; `f[0]` is all ones for the last block
+/// and `f[1]` for the last node.
+pub fn blake2s_compress(h: &mut [u32; 8], m: &[u32; 16], t: &[u32; 2], f: &[u32; 2], rounds: u32) {
+    reduce_rounds(h, m, t, f, rounds);
+}
@@ -0,0 +1,85 @@
+//! BLAKE2sp, the 8-way parallel mode of BLAKE2s. The input is split into blocks which are dealt to 8 leaves in turn,
+//! and the root hashes the concatenated leaf outputs.
+
+use crate::blake2s::{blake2s, blake2s_unkeyed, Blake2s, Param2s};
+
+/// Degree of parallelism.
+const BLAKE2SP_PP: usize = 8;
+/// Block bytes.
+const BLAKE2SP_BB: usize = 64;
+/// Hash bytes.
+const BLAKE2SP_NN: u8 = 32;
+
+/// A context for computing the BLAKE2sp checksum.
+#[derive(Clone)]
+pub struct Blake2sp {
+    /// Leaves, the i-th one hashes blocks i, i + 8, i + 16, ... of the input.
+    s: [Blake2s; BLAKE2SP_PP],
+    /// Root.
+    r: Blake2s,
+    /// Message byte offset modulo the length of a round of blocks over all leaves.
+    o: usize,
+}
+
+impl Blake2sp {
+    /// Update this hash object's state with the provided data.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            let n = (BLAKE2SP_BB - self.o % BLAKE2SP_BB).min(data.len());
+            self.s[self.o / BLAKE2SP_BB].update(&data[..n]);
+            self.o = (self.o + n) % (BLAKE2SP_PP * BLAKE2SP_BB);
+            data = &data[n..];
+        }
+    }
+
+    /// Update this hash object's state with the provided data, hashing the leaves on the threads of the current rayon
+    /// pool.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn update_rayon(&mut self, data: &[u8]) {
+        use rayon::prelude::*;
+        let a = ((BLAKE2SP_PP * BLAKE2SP_BB - self.o) % (BLAKE2SP_PP * BLAKE2SP_BB)).min(data.len());
+        self.update(&data[..a]);
+        let data = &data[a..];
+        let m = data.len() - data.len() % (BLAKE2SP_PP * BLAKE2SP_BB);
+        self.s.par_iter_mut().enumerate().for_each(|(i, s)| {
+            for b in data[..m].chunks(BLAKE2SP_BB).skip(i).step_by(BLAKE2SP_PP) {
+                s.update(b);
+            }
+        });
+        self.update(&data[m..]);
+    }
+
+    /// Return the digest value.
+    pub fn digest(&mut self, d: &mut [u8]) {
+        for s in &mut self.s {
+            self.r.update(&s.finalize());
+        }
+        self.r.digest(d);
+    }
+}
+
+/// Core hasher state of BLAKE2sp. The digest length, key, salt and personalization are taken from the parameter block,
+/// tree parameters are set by this function.
+pub fn blake2sp(param2s: Param2s) -> Blake2sp {
+    let mut p = param2s;
+    p.fanout(BLAKE2SP_PP as u8);
+    p.depth(2);
+    p.leaf_length(0);
+    p.inner_length(BLAKE2SP_NN);
+    let s = core::array::from_fn(|i| {
+        let mut p = p.clone();
+        p.node_offset(i as u64);
+        p.node_depth(0);
+        let mut s = blake2s(p);
+        if i == BLAKE2SP_PP - 1 {
+            s.last_node();
+        }
+        s
+    });
+    p.node_offset(0);
+    p.node_depth(1);
+    let mut r = blake2s_unkeyed(&p);
+    r.last_node();
+    Blake2sp { s, r, o: 0 }
+}
@@ -0,0 +1,56 @@
+//! BLAKE2Xb, the extendable output function built on BLAKE2b. The input is hashed once into a 64-byte root, and
+//! output blocks are digests of that root at consecutive node offsets.
+
+use crate::blake2b::{blake2b, Blake2b, Param2b};
+
+/// Output length when it is not known in advance.
+pub const BLAKE2XB_UNKNOWN: u32 = u32::MAX;
+/// Output block bytes.
+const BLAKE2XB_NN: usize = 64;
+
+/// A context for computing the BLAKE2Xb output.
+#[derive(Clone)]
+pub struct Blake2xb {
+    /// Input node.
+    h: Blake2b,
+    /// Parameters of the input node, which output nodes derive from.
+    p: Param2b,
+    /// Output byte length, or BLAKE2XB_UNKNOWN.
+    n: u32,
+}
+
+impl Blake2xb {
+    /// Update this hash object's state with the provided data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+
+    /// Return the output. Its length must be the one given to blake2xb(), or at most 2^32 blocks of 64 bytes if the
+    /// length was unknown.
+    pub fn digest(&mut self, d: &mut [u8]) {
+        if self.n == BLAKE2XB_UNKNOWN {
+            assert!(d.len() as u64 <= (BLAKE2XB_NN as u64) << 32);
+        } else {
+            assert_eq!(d.len() as u64, self.n as u64);
+        }
+        let h = self.h.finalize();
+        for (i, c) in d.chunks_mut(BLAKE2XB_NN).enumerate() {
+            let mut p = self.p.clone();
+            p.xof_node(i as u32, c.len() as u8);
+            let mut b = blake2b(p);
+            b.update(&h);
+            c.copy_from_slice(&b.finalize()[..c.len()]);
+        }
+    }
+}
+
+/// Core hasher state of BLAKE2Xb. The key, salt and personalization are taken from the parameter block, the digest
+/// length is set by this function. The output byte length n is an integer in [1, 2^32 - 2], or BLAKE2XB_UNKNOWN.
+pub fn blake2xb(param2b: Param2b, n: u32) -> Blake2xb {
+    assert!(n != 0);
+    let mut p = param2b;
+    p.digest(BLAKE2XB_NN as u8);
+    p.xof_length(n);
+    let h = blake2b(p.clone());
+    Blake2xb { h, p, n }
+}
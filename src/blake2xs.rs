@@ -0,0 +1,56 @@
+//! BLAKE2Xs, the extendable output function built on BLAKE2s. The input is hashed once into a 32-byte root, and
+//! output blocks are digests of that root at consecutive node offsets.
+
+use crate::blake2s::{blake2s, Blake2s, Param2s};
+
+/// Output length when it is not known in advance.
+pub const BLAKE2XS_UNKNOWN: u16 = u16::MAX;
+/// Output block bytes.
+const BLAKE2XS_NN: usize = 32;
+
+/// A context for computing the BLAKE2Xs output.
+#[derive(Clone)]
+pub struct Blake2xs {
+    /// Input node.
+    h: Blake2s,
+    /// Parameters of the input node, which output nodes derive from.
+    p: Param2s,
+    /// Output byte length, or BLAKE2XS_UNKNOWN.
+    n: u16,
+}
+
+impl Blake2xs {
+    /// Update this hash object's state with the provided data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+
+    /// Return the output. Its length must be the one given to blake2xs(), or at most 2^32 blocks of 32 bytes if the
+    /// length was unknown.
+    pub fn digest(&mut self, d: &mut [u8]) {
+        if self.n == BLAKE2XS_UNKNOWN {
+            assert!(d.len() as u64 <= (BLAKE2XS_NN as u64) << 32);
+        } else {
+            assert_eq!(d.len() as u64, self.n as u64);
+        }
+        let h = self.h.finalize();
+        for (i, c) in d.chunks_mut(BLAKE2XS_NN).enumerate() {
+            let mut p = self.p.clone();
+            p.xof_node(i as u32, c.len() as u8);
+            let mut b = blake2s(p);
+            b.update(&h);
+            c.copy_from_slice(&b.finalize()[..c.len()]);
+        }
+    }
+}
+
+/// Core hasher state of BLAKE2Xs. The key, salt and personalization are taken from the parameter block, the digest
+/// length is set by this function. The output byte length n is an integer in [1, 2^16 - 2], or BLAKE2XS_UNKNOWN.
+pub fn blake2xs(param2s: Param2s, n: u16) -> Blake2xs {
+    assert!(n != 0);
+    let mut p = param2s;
+    p.digest(BLAKE2XS_NN as u8);
+    p.xof_length(n);
+    let h = blake2s(p.clone());
+    Blake2xs { h, p, n }
+}
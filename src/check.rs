@@ -0,0 +1,280 @@
+//! Verify checksum manifests as written by b2sum, in either the GNU or the BSD line format.
+
+use crate::sum::{escape, sum_params, Algorithm, SumParams};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::vec::Vec;
+
+/// One properly formatted manifest line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub algorithm: Algorithm,
+    /// Digest byte length.
+    pub length: usize,
+    pub path: PathBuf,
+    pub digest: Vec<u8>,
+}
+
+/// Decode a hex digest of at most a.size() bytes.
+fn unhex(s: &[u8], a: Algorithm) -> Option<Vec<u8>> {
+    if s.is_empty() || !s.len().is_multiple_of(2) || s.len() > a.size() * 2 {
+        return None;
+    }
+    let n = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    s.chunks(2).map(|c| Some(n(c[0])? << 4 | n(c[1])?)).collect()
+}
+
+/// Reverse escape().
+fn unescape(s: &[u8]) -> Option<Vec<u8>> {
+    let mut r = Vec::with_capacity(s.len());
+    let mut i = s.iter();
+    while let Some(&c) = i.next() {
+        r.push(match c {
+            b'\\' => match i.next()? {
+                b'\\' => b'\\',
+                b'n' => b'\n',
+                b'r' => b'\r',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+    Some(r)
+}
+
+#[cfg(unix)]
+fn path(b: Vec<u8>) -> Option<PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+    Some(PathBuf::from(std::ffi::OsString::from_vec(b)))
+}
+
+#[cfg(not(unix))]
+fn path(b: Vec<u8>) -> Option<PathBuf> {
+    std::string::String::from_utf8(b).ok().map(PathBuf::from)
+}
+
+/// Parse a BSD line such as "BLAKE2b-256 (file) = hex", after the algorithm name.
+fn parse_tag(s: &[u8], a: Algorithm) -> Option<(Vec<u8>, Vec<u8>)> {
+    let (bits, s) = match s.strip_prefix(b"-") {
+        Some(s) => {
+            let i = s.iter().position(|c| !c.is_ascii_digit())?;
+            let bits = std::str::from_utf8(&s[..i]).ok()?.parse::<usize>().ok()?;
+            if bits == 0 || !bits.is_multiple_of(8) {
+                return None;
+            }
+            (Some(bits), &s[i..])
+        }
+        None => (None, s),
+    };
+    let s = s.strip_prefix(b" (")?;
+    let i = s.windows(4).rposition(|w| w == b") = ")?;
+    let digest = unhex(&s[i + 4..], a)?;
+    if bits.is_some_and(|bits| bits != digest.len() * 8) {
+        return None;
+    }
+    Some((s[..i].to_vec(), digest))
+}
+
+/// Parse a GNU line such as "hex  file" or "hex *file".
+fn parse_gnu(s: &[u8], a: Algorithm) -> Option<(Vec<u8>, Vec<u8>)> {
+    let i = s.iter().position(|&c| c == b' ' || c == b'\t')?;
+    let digest = unhex(&s[..i], a)?;
+    let s = &s[i + 1..];
+    let s = match s.first() {
+        Some(b' ' | b'*') => &s[1..],
+        _ => s,
+    };
+    if s.is_empty() {
+        return None;
+    }
+    Some((s.to_vec(), digest))
+}
+
+/// Parse one manifest line, without its line terminator. GNU lines hold no algorithm name and are taken to be of
+/// algorithm a; BSD lines name their own. The digest length is inferred from the number of hex digits, like b2sum -c
+/// does. None if the line is not properly formatted.
+pub fn parse_line(line: &[u8], a: Algorithm) -> Option<Entry> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let line = &line[line.iter().position(|&c| c != b' ' && c != b'\t')?..];
+    let (escaped, line) = match line.strip_prefix(b"\\") {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    let tag = [Algorithm::Blake2bp, Algorithm::Blake2sp, Algorithm::Blake2b, Algorithm::Blake2s]
+        .into_iter()
+        .find_map(|t| line.strip_prefix(t.name().as_bytes()).and_then(|s| Some((t, parse_tag(s, t)?))));
+    let (algorithm, (name, digest)) = match tag {
+        Some(r) => r,
+        None => (a, parse_gnu(line, a)?),
+    };
+    let name = if escaped { unescape(&name)? } else { name };
+    Some(Entry { algorithm, length: digest.len(), path: path(name)?, digest })
+}
+
+/// Result of checking one file.
+#[derive(Debug)]
+pub enum Status {
+    Ok,
+    Failed,
+    /// The file could not be opened or read.
+    Missing(std::io::Error),
+}
+
+/// Hash the file of an entry and compare it with the expected digest. A path of "-" is read from standard input.
+pub fn verify(e: &Entry) -> Status {
+    verify_params(e, &SumParams::default())
+}
+
+/// Like verify(), with a key, salt or personalization.
+fn verify_params(e: &Entry, p: &SumParams) -> Status {
+    let r = if e.path == Path::new("-") {
+        sum_params(e.algorithm, e.length, p, std::io::stdin().lock())
+    } else {
+        std::fs::File::open(&e.path).and_then(|f| sum_params(e.algorithm, e.length, p, f))
+    };
+    match r {
+        Ok(d) if d == e.digest => Status::Ok,
+        Ok(_) => Status::Failed,
+        Err(e) => Status::Missing(e),
+    }
+}
+
+/// Options for checking manifests, with the semantics of b2sum -c.
+pub struct Check {
+    a: Algorithm,
+    p: SumParams,
+    quiet: bool,
+    status: bool,
+    strict: bool,
+    ignore_missing: bool,
+}
+
+/// Counts of the lines of one manifest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub ok: usize,
+    pub failed: usize,
+    /// Files that could not be opened or read.
+    pub missing: usize,
+    /// Files that do not exist and were skipped because of ignore_missing.
+    pub ignored: usize,
+    /// Lines that are not properly formatted.
+    pub malformed: usize,
+    strict: bool,
+}
+
+impl Summary {
+    /// Whether the manifest passed: at least one properly formatted line, no failed or missing files, and no malformed
+    /// lines in strict mode. With ignore_missing, at least one file must also have been verified.
+    pub fn success(&self) -> bool {
+        self.ok + self.failed + self.missing > 0
+            && self.failed == 0
+            && self.missing == 0
+            && !(self.strict && self.malformed > 0)
+    }
+}
+
+impl Check {
+    /// Set the algorithm of GNU lines, which do not name it. The default is BLAKE2b.
+    pub fn algorithm(&mut self, a: Algorithm) {
+        self.a = a;
+    }
+
+    /// Set the key, salt and personalization the manifest was computed with. Lines of algorithms whose limits these
+    /// exceed are not properly formatted. BLAKE2X manifests are not supported.
+    pub fn params(&mut self, p: SumParams) {
+        assert!(!p.xof);
+        self.p = p;
+    }
+
+    /// Do not report files that are OK.
+    pub fn quiet(&mut self, b: bool) {
+        self.quiet = b;
+    }
+
+    /// Do not report anything. The outcome is only in the summary.
+    pub fn status(&mut self, b: bool) {
+        self.status = b;
+    }
+
+    /// Fail if any line is not properly formatted.
+    pub fn strict(&mut self, b: bool) {
+        self.strict = b;
+    }
+
+    /// Skip files that do not exist instead of failing.
+    pub fn ignore_missing(&mut self, b: bool) {
+        self.ignore_missing = b;
+    }
+
+    /// Verify every entry of a manifest. For each file, "name: OK", "name: FAILED" or "name: FAILED open or read" is
+    /// written to out as b2sum -c does, and the error of files that could not be read is passed to warn, even with
+    /// status.
+    pub fn run(
+        &self,
+        manifest: impl BufRead,
+        mut out: impl Write,
+        mut warn: impl FnMut(&Path, &std::io::Error),
+    ) -> std::io::Result<Summary> {
+        let mut s = Summary { strict: self.strict, ..Summary::default() };
+        for line in manifest.split(b'\n') {
+            let line = line?;
+            if line.first() == Some(&b'#') || line.iter().all(|c| c.is_ascii_whitespace()) {
+                continue;
+            }
+            let Some(e) = parse_line(&line, self.a).filter(|e| self.p.fits(e.algorithm)) else {
+                s.malformed += 1;
+                continue;
+            };
+            let r = match verify_params(&e, &self.p) {
+                Status::Ok => {
+                    s.ok += 1;
+                    if self.quiet {
+                        continue;
+                    }
+                    &b"OK"[..]
+                }
+                Status::Failed => {
+                    s.failed += 1;
+                    &b"FAILED"[..]
+                }
+                Status::Missing(err) => {
+                    if self.ignore_missing && err.kind() == std::io::ErrorKind::NotFound {
+                        s.ignored += 1;
+                        continue;
+                    }
+                    s.missing += 1;
+                    out.flush()?;
+                    warn(&e.path, &err);
+                    &b"FAILED open or read"[..]
+                }
+            };
+            if self.status {
+                continue;
+            }
+            let name = e.path.as_os_str().as_encoded_bytes();
+            // Like b2sum, names with a backslash, newline or carriage return are escaped as in checksum lines.
+            let escaped = escape(name);
+            if escaped.is_some() {
+                out.write_all(b"\\")?;
+            }
+            out.write_all(escaped.as_deref().unwrap_or(name))?;
+            out.write_all(b": ")?;
+            out.write_all(r)?;
+            out.write_all(b"\n")?;
+        }
+        Ok(s)
+    }
+}
+
+/// Default options: BLAKE2b for GNU lines, and every file reported.
+pub fn check() -> Check {
+    Check {
+        a: Algorithm::Blake2b,
+        p: SumParams::default(),
+        quiet: false,
+        status: false,
+        strict: false,
+        ignore_missing: false,
+    }
+}
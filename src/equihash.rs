@@ -0,0 +1,150 @@
+//! Verification of Equihash proof-of-work solutions as used by Zcash, without allocation.
+
+use crate::blake2b::{blake2b, blake2b_params, Blake2b};
+
+/// Most indices of a solution: 2^9, for k up to 9.
+const EQUIHASH_INDICES: usize = 512;
+
+/// Errors of Equihash solutions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EquihashError {
+    /// The solution does not have 2^k indices, or is not `solution_len()` bytes.
+    Length,
+    /// Two sibling subtrees do not collide on their next bits.
+    Collision,
+    /// The first index of a right subtree is below the first index of its left sibling.
+    Order,
+    /// An index appears in both sibling subtrees.
+    Duplicate,
+    /// The XOR of all hashes is not zero.
+    NonZero,
+}
+
+impl core::fmt::Display for EquihashError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Self::Length => "invalid solution length",
+            Self::Collision => "invalid collision length between StepRows",
+            Self::Order => "index tree incorrectly ordered",
+            Self::Duplicate => "duplicate indices",
+            Self::NonZero => "root hash of tree is non-zero",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for EquihashError {}
+
+/// Equihash with parameters n and k. The BLAKE2b state absorbs the block header and nonce once; each index hashes a
+/// clone of this midstate.
+#[derive(Clone)]
+pub struct Equihash {
+    n: u32,
+    k: u32,
+    h: Blake2b,
+}
+
+impl Equihash {
+    /// Absorb the block header and nonce.
+    pub fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+
+    /// Bits of each collision.
+    fn collision_bits(&self) -> usize {
+        (self.n / (self.k + 1)) as usize
+    }
+
+    /// Bytes of a solution in the minimal encoding: 2^k indices of `n / (k + 1) + 1` big-endian bits.
+    pub fn solution_len(&self) -> usize {
+        (1 << self.k) * (self.collision_bits() + 1) / 8
+    }
+
+    /// Hash of index i, n/8 bytes.
+    pub fn hash(&self, i: u32, out: &mut [u8]) {
+        assert_eq!(out.len(), self.n as usize / 8);
+        let m = 512 / self.n;
+        let mut h = self.h.clone();
+        h.update(&(i / m).to_le_bytes());
+        let mut r = [0; 64];
+        let l = (m * self.n / 8) as usize;
+        h.digest(&mut r[..l]);
+        let s = ((i % m) * self.n / 8) as usize;
+        out.copy_from_slice(&r[s..s + out.len()]);
+    }
+
+    /// Verify a solution in the minimal encoding, as found in Zcash block headers.
+    pub fn verify(&self, solution: &[u8]) -> Result<(), EquihashError> {
+        if solution.len() != self.solution_len() {
+            return Err(EquihashError::Length);
+        }
+        let w = self.collision_bits() + 1;
+        let mut idx = [0; EQUIHASH_INDICES];
+        let idx = &mut idx[..1 << self.k];
+        for (j, x) in idx.iter_mut().enumerate() {
+            for b in j * w..(j + 1) * w {
+                *x = (*x << 1) | (solution[b / 8] >> (7 - b % 8) & 1) as u32;
+            }
+        }
+        self.verify_indices(idx)
+    }
+
+    /// Verify a solution given as its 2^k indices.
+    pub fn verify_indices(&self, idx: &[u32]) -> Result<(), EquihashError> {
+        if idx.len() != 1 << self.k {
+            return Err(EquihashError::Length);
+        }
+        let r = self.node(idx)?;
+        if !zero_bits(&r, self.k as usize * self.collision_bits(), self.n as usize) {
+            return Err(EquihashError::NonZero);
+        }
+        Ok(())
+    }
+
+    /// XOR of the hashes of a subtree, after checking its merges, depth first and left to right.
+    fn node(&self, idx: &[u32]) -> Result<[u8; 64], EquihashError> {
+        let mut r = [0; 64];
+        if idx.len() == 1 {
+            self.hash(idx[0], &mut r[..self.n as usize / 8]);
+            return Ok(r);
+        }
+        let (a, b) = idx.split_at(idx.len() / 2);
+        let x = self.node(a)?;
+        let y = self.node(b)?;
+        r.iter_mut().zip(x.iter().zip(y)).for_each(|(r, (x, y))| *r = x ^ y);
+        // Merges at height l collide on bits (l - 1) * c to l * c.
+        let c = self.collision_bits();
+        let l = a.len().trailing_zeros() as usize + 1;
+        if !zero_bits(&r, (l - 1) * c, l * c) {
+            return Err(EquihashError::Collision);
+        }
+        if b[0] < a[0] {
+            return Err(EquihashError::Order);
+        }
+        if a.iter().any(|i| b.contains(i)) {
+            return Err(EquihashError::Duplicate);
+        }
+        Ok(r)
+    }
+}
+
+/// Whether bits from..to of x, most significant first, are zero.
+fn zero_bits(x: &[u8], from: usize, to: usize) -> bool {
+    (from..to).all(|b| x[b / 8] >> (7 - b % 8) & 1 == 0)
+}
+
+/// Create an Equihash verifier, such as (200, 9) for Zcash, (144, 5) for Bitcoin Gold or (96, 5). The BLAKE2b
+/// personalization is "ZcashPoW" followed by n and k as little-endian 32-bit integers.
+pub fn equihash(n: u32, k: u32) -> Equihash {
+    assert!(n.is_multiple_of(8) && n <= 512);
+    assert!((3..=9).contains(&k) && k < n);
+    assert!(n.is_multiple_of(k + 1) && n / (k + 1) < 32);
+    let mut person = [0; 16];
+    person[..8].copy_from_slice(b"ZcashPoW");
+    person[8..12].copy_from_slice(&n.to_le_bytes());
+    person[12..].copy_from_slice(&k.to_le_bytes());
+    let mut p = blake2b_params();
+    p.digest(((512 / n) * n / 8) as u8);
+    p.person(&person);
+    Equihash { n, k, h: blake2b(p) }
+}
@@ -0,0 +1,98 @@
+//! Keyed BLAKE2s as a [`Hasher`], for hash tables exposed to untrusted keys.
+
+use crate::blake2s::{blake2s, blake2s_params, Blake2s};
+use core::hash::{BuildHasher, Hasher};
+
+/// A [`Hasher`] computing keyed BLAKE2s with an 8 byte digest. Integers are written in little endian so the result
+/// does not depend on the platform.
+#[derive(Clone)]
+pub struct Blake2sHasher {
+    h: Blake2s,
+}
+
+impl Blake2sHasher {
+    /// Create a hasher with the given key. Key length in [0, 32].
+    pub fn new(key: &[u8]) -> Self {
+        let mut p = blake2s_params();
+        p.digest(8);
+        p.key(key);
+        Self { h: blake2s(p) }
+    }
+}
+
+impl Hasher for Blake2sHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.h.update(bytes);
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.h.update(&[i]);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.h.update(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.h.update(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.h.update(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.h.update(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.h.update(&(i as u64).to_le_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        let mut r = [0; 8];
+        self.h.clone().digest(&mut r);
+        u64::from_le_bytes(r)
+    }
+}
+
+/// A [`BuildHasher`] creating [`Blake2sHasher`]s that all share the same key.
+#[derive(Clone)]
+pub struct RandomBlake2sState {
+    /// Hasher state right after keying.
+    h: Blake2sHasher,
+}
+
+impl RandomBlake2sState {
+    /// Create a state keyed with a random key. The key is read from the operating system once per process.
+    #[cfg(feature = "std")]
+    pub fn new() -> Self {
+        static KEY: std::sync::OnceLock<[u8; 32]> = std::sync::OnceLock::new();
+        let k = KEY.get_or_init(|| {
+            let mut k = [0; 32];
+            getrandom::getrandom(&mut k).expect("failed to generate random key");
+            k
+        });
+        Self::with_key(k)
+    }
+
+    /// Create a state with a fixed key, for deterministic builds. Key length in [0, 32].
+    pub fn with_key(key: &[u8]) -> Self {
+        Self { h: Blake2sHasher::new(key) }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for RandomBlake2sState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for RandomBlake2sState {
+    type Hasher = Blake2sHasher;
+
+    fn build_hasher(&self) -> Blake2sHasher {
+        self.h.clone()
+    }
+}
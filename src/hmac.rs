@@ -0,0 +1,203 @@
+//! HMAC (RFC 2104), HKDF (RFC 5869) and PBKDF2 (RFC 8018) over unkeyed BLAKE2b-512 and BLAKE2s-256, with the BLAKE2
+//! block sizes of 128 and 64 bytes. These are the constructions of Noise and WireGuard, which use HMAC rather than the
+//! native keyed mode of BLAKE2.
+
+use crate::blake2b::{blake2b, blake2b_params, Blake2b};
+use crate::blake2s::{blake2s, blake2s_params, Blake2s};
+
+/// Block bytes of BLAKE2b.
+const HMAC_BLAKE2B_BB: usize = 128;
+/// Hash bytes of BLAKE2b.
+const HMAC_BLAKE2B_NN: usize = 64;
+/// Block bytes of BLAKE2s.
+const HMAC_BLAKE2S_BB: usize = 64;
+/// Hash bytes of BLAKE2s.
+const HMAC_BLAKE2S_NN: usize = 32;
+
+fn blake2b_512() -> Blake2b {
+    let mut p = blake2b_params();
+    p.digest(HMAC_BLAKE2B_NN as u8);
+    blake2b(p)
+}
+
+fn blake2s_256() -> Blake2s {
+    let mut p = blake2s_params();
+    p.digest(HMAC_BLAKE2S_NN as u8);
+    blake2s(p)
+}
+
+/// A context for computing HMAC-BLAKE2b.
+#[derive(Clone)]
+pub struct HmacBlake2b {
+    /// Inner hash, after the key block xor ipad.
+    i: Blake2b,
+    /// Outer hash, after the key block xor opad.
+    o: Blake2b,
+}
+
+impl HmacBlake2b {
+    /// Update this hash object's state with the provided data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.i.update(data);
+    }
+
+    /// Return the 64-byte tag.
+    pub fn digest(&mut self, d: &mut [u8]) {
+        let mut r = [0; HMAC_BLAKE2B_NN];
+        self.i.digest(&mut r);
+        self.o.update(&r);
+        self.o.digest(d);
+    }
+}
+
+/// Core hasher state of HMAC-BLAKE2b. Keys longer than 128 bytes are hashed first.
+pub fn hmac_blake2b(key: &[u8]) -> HmacBlake2b {
+    let mut k = [0; HMAC_BLAKE2B_BB];
+    if key.len() > HMAC_BLAKE2B_BB {
+        let mut h = blake2b_512();
+        h.update(key);
+        h.digest(&mut k[..HMAC_BLAKE2B_NN]);
+    } else {
+        k[..key.len()].copy_from_slice(key);
+    }
+    let mut i = blake2b_512();
+    let mut o = blake2b_512();
+    i.update(&k.map(|x| x ^ 0x36));
+    o.update(&k.map(|x| x ^ 0x5c));
+    HmacBlake2b { i, o }
+}
+
+/// HKDF-Extract with HMAC-BLAKE2b. Returns the 64-byte pseudorandom key.
+pub fn hkdf_blake2b_extract(salt: &[u8], ikm: &[u8]) -> [u8; HMAC_BLAKE2B_NN] {
+    let mut h = hmac_blake2b(salt);
+    h.update(ikm);
+    let mut r = [0; HMAC_BLAKE2B_NN];
+    h.digest(&mut r);
+    r
+}
+
+/// HKDF-Expand with HMAC-BLAKE2b. Fills okm, of at most 255 * 64 bytes.
+pub fn hkdf_blake2b_expand(prk: &[u8], info: &[u8], okm: &mut [u8]) {
+    assert!(okm.len() <= 255 * HMAC_BLAKE2B_NN);
+    let k = hmac_blake2b(prk);
+    let mut t = [0; HMAC_BLAKE2B_NN];
+    for (i, c) in okm.chunks_mut(HMAC_BLAKE2B_NN).enumerate() {
+        let mut h = k.clone();
+        if i != 0 {
+            h.update(&t);
+        }
+        h.update(info);
+        h.update(&[i as u8 + 1]);
+        h.digest(&mut t);
+        c.copy_from_slice(&t[..c.len()]);
+    }
+}
+
+/// PBKDF2 with HMAC-BLAKE2b as the pseudorandom function. Fills out with the derived key.
+pub fn pbkdf2_blake2b(password: &[u8], salt: &[u8], rounds: u32, out: &mut [u8]) {
+    assert!(rounds >= 1);
+    let k = hmac_blake2b(password);
+    for (i, c) in out.chunks_mut(HMAC_BLAKE2B_NN).enumerate() {
+        let mut h = k.clone();
+        h.update(salt);
+        h.update(&(i as u32 + 1).to_be_bytes());
+        let mut u = [0; HMAC_BLAKE2B_NN];
+        h.digest(&mut u);
+        let mut t = u;
+        for _ in 1..rounds {
+            let mut h = k.clone();
+            h.update(&u);
+            h.digest(&mut u);
+            t.iter_mut().zip(u).for_each(|(t, u)| *t ^= u);
+        }
+        c.copy_from_slice(&t[..c.len()]);
+    }
+}
+
+/// A context for computing HMAC-BLAKE2s.
+#[derive(Clone)]
+pub struct HmacBlake2s {
+    /// Inner hash, after the key block xor ipad.
+    i: Blake2s,
+    /// Outer hash, after the key block xor opad.
+    o: Blake2s,
+}
+
+impl HmacBlake2s {
+    /// Update this hash object's state with the provided data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.i.update(data);
+    }
+
+    /// Return the 32-byte tag.
+    pub fn digest(&mut self, d: &mut [u8]) {
+        let mut r = [0; HMAC_BLAKE2S_NN];
+        self.i.digest(&mut r);
+        self.o.update(&r);
+        self.o.digest(d);
+    }
+}
+
+/// Core hasher state of HMAC-BLAKE2s. Keys longer than 64 bytes are hashed first.
+pub fn hmac_blake2s(key: &[u8]) -> HmacBlake2s {
+    let mut k = [0; HMAC_BLAKE2S_BB];
+    if key.len() > HMAC_BLAKE2S_BB {
+        let mut h = blake2s_256();
+        h.update(key);
+        h.digest(&mut k[..HMAC_BLAKE2S_NN]);
+    } else {
+        k[..key.len()].copy_from_slice(key);
+    }
+    let mut i = blake2s_256();
+    let mut o = blake2s_256();
+    i.update(&k.map(|x| x ^ 0x36));
+    o.update(&k.map(|x| x ^ 0x5c));
+    HmacBlake2s { i, o }
+}
+
+/// HKDF-Extract with HMAC-BLAKE2s. Returns the 32-byte pseudorandom key.
+pub fn hkdf_blake2s_extract(salt: &[u8], ikm: &[u8]) -> [u8; HMAC_BLAKE2S_NN] {
+    let mut h = hmac_blake2s(salt);
+    h.update(ikm);
+    let mut r = [0; HMAC_BLAKE2S_NN];
+    h.digest(&mut r);
+    r
+}
+
+/// HKDF-Expand with HMAC-BLAKE2s. Fills okm, of at most 255 * 32 bytes.
+pub fn hkdf_blake2s_expand(prk: &[u8], info: &[u8], okm: &mut [u8]) {
+    assert!(okm.len() <= 255 * HMAC_BLAKE2S_NN);
+    let k = hmac_blake2s(prk);
+    let mut t = [0; HMAC_BLAKE2S_NN];
+    for (i, c) in okm.chunks_mut(HMAC_BLAKE2S_NN).enumerate() {
+        let mut h = k.clone();
+        if i != 0 {
+            h.update(&t);
+        }
+        h.update(info);
+        h.update(&[i as u8 + 1]);
+        h.digest(&mut t);
+        c.copy_from_slice(&t[..c.len()]);
+    }
+}
+
+/// PBKDF2 with HMAC-BLAKE2s as the pseudorandom function. Fills out with the derived key.
+pub fn pbkdf2_blake2s(password: &[u8], salt: &[u8], rounds: u32, out: &mut [u8]) {
+    assert!(rounds >= 1);
+    let k = hmac_blake2s(password);
+    for (i, c) in out.chunks_mut(HMAC_BLAKE2S_NN).enumerate() {
+        let mut h = k.clone();
+        h.update(salt);
+        h.update(&(i as u32 + 1).to_be_bytes());
+        let mut u = [0; HMAC_BLAKE2S_NN];
+        h.digest(&mut u);
+        let mut t = u;
+        for _ in 1..rounds {
+            let mut h = k.clone();
+            h.update(&u);
+            h.digest(&mut u);
+            t.iter_mut().zip(u).for_each(|(t, u)| *t ^= u);
+        }
+        c.copy_from_slice(&t[..c.len()]);
+    }
+}
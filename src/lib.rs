@@ -37,7 +37,96 @@
 //! assert_eq!(r, e);
 //! ```
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod argon2;
 mod blake2b;
+mod blake2bp;
 mod blake2s;
-pub use blake2b::{blake2b, blake2b_params};
-pub use blake2s::{blake2s, blake2s_params};
+mod blake2sp;
+mod blake2xb;
+mod blake2xs;
+#[cfg(feature = "std")]
+mod check;
+mod eip152;
+mod equihash;
+mod hasher;
+mod hmac;
+mod merkle;
+mod mmr;
+mod noise;
+#[cfg(feature = "std")]
+mod outboard;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pow;
+#[cfg(feature = "digest")]
+mod rustcrypto;
+mod selftest;
+mod sodium;
+mod substrate;
+#[cfg(feature = "std")]
+mod sum;
+#[cfg(feature = "std")]
+mod tree;
+mod wireguard;
+pub use argon2::{argon2, Argon2, Argon2Block, Argon2Error, Argon2Type, ARGON2_VERSION};
+pub use blake2b::{blake2b, blake2b_compress, blake2b_const, blake2b_long, blake2b_params, Blake2b, Param2b};
+pub use blake2bp::{blake2bp, Blake2bp};
+pub use blake2s::{blake2s, blake2s_compress, blake2s_const, blake2s_params, Blake2s, Param2s};
+pub use blake2sp::{blake2sp, Blake2sp};
+pub use blake2xb::{blake2xb, Blake2xb, BLAKE2XB_UNKNOWN};
+pub use blake2xs::{blake2xs, Blake2xs, BLAKE2XS_UNKNOWN};
+#[cfg(feature = "std")]
+pub use check::{check, parse_line, verify, Check, Entry, Status, Summary};
+pub use eip152::{eip152_blake2_f, eip152_gas, Eip152Error, EIP152_INPUT_LEN};
+pub use equihash::{equihash, Equihash, EquihashError};
+pub use hasher::{Blake2sHasher, RandomBlake2sState};
+pub use hmac::{
+    hkdf_blake2b_expand, hkdf_blake2b_extract, hkdf_blake2s_expand, hkdf_blake2s_extract, hmac_blake2b, hmac_blake2s,
+    pbkdf2_blake2b, pbkdf2_blake2s, HmacBlake2b, HmacBlake2s,
+};
+#[cfg(feature = "std")]
+pub use merkle::MerkleTree;
+pub use merkle::{merkle, Merkle, MerkleError, MerkleLayout};
+#[cfg(feature = "std")]
+pub use mmr::MmrMemory;
+pub use mmr::{mmr, mmr_leaf_hash, mmr_node_hash, Mmr, MmrBackend, MmrError, MmrProof};
+pub use noise::{
+    noise_hash_blake2b, noise_hash_blake2s, noise_hkdf2_blake2b, noise_hkdf2_blake2s, noise_hkdf3_blake2b,
+    noise_hkdf3_blake2s, noise_hmac_blake2b, noise_hmac_blake2s, SymmetricState2b, SymmetricState2s,
+};
+#[cfg(feature = "std")]
+pub use outboard::{outboard, Outboard, OutboardError, OutboardReader};
+#[cfg(feature = "rayon")]
+pub use parallel::{parallel, Parallel};
+pub use pow::{pow, Pow};
+#[cfg(feature = "digest")]
+pub use rustcrypto::{
+    Blake2b256, Blake2b512, Blake2bFix, Blake2bMac, Blake2bMac512, Blake2bVar, Blake2s128, Blake2s256, Blake2sFix,
+    Blake2sMac, Blake2sMac256, Blake2sVar,
+};
+pub use selftest::{selftest, SelftestError};
+pub use sodium::{
+    crypto_generichash, crypto_generichash_blake2b_init_salt_personal, crypto_generichash_blake2b_salt_personal,
+    crypto_generichash_init, crypto_kdf_derive_from_key, Generichash, SodiumError, CRYPTO_GENERICHASH_BLAKE2B_BYTES,
+    CRYPTO_GENERICHASH_BLAKE2B_BYTES_MAX, CRYPTO_GENERICHASH_BLAKE2B_BYTES_MIN, CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES,
+    CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES_MAX, CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES_MIN,
+    CRYPTO_GENERICHASH_BLAKE2B_PERSONALBYTES, CRYPTO_GENERICHASH_BLAKE2B_SALTBYTES, CRYPTO_GENERICHASH_BYTES,
+    CRYPTO_GENERICHASH_BYTES_MAX, CRYPTO_GENERICHASH_BYTES_MIN, CRYPTO_GENERICHASH_KEYBYTES,
+    CRYPTO_GENERICHASH_KEYBYTES_MAX, CRYPTO_GENERICHASH_KEYBYTES_MIN, CRYPTO_KDF_BLAKE2B_BYTES_MAX,
+    CRYPTO_KDF_BLAKE2B_BYTES_MIN, CRYPTO_KDF_BLAKE2B_CONTEXTBYTES, CRYPTO_KDF_BLAKE2B_KEYBYTES, CRYPTO_KDF_BYTES_MAX,
+    CRYPTO_KDF_BYTES_MIN, CRYPTO_KDF_CONTEXTBYTES, CRYPTO_KDF_KEYBYTES,
+};
+pub use substrate::{
+    blake2_128, blake2_128_concat, blake2_256, blake2_512, ss58_decode, ss58_encode, ss58_hash, Ss58, Ss58Error,
+};
+#[cfg(feature = "std")]
+pub use sum::{escape, sum, sum_params, Algorithm, SumParams};
+#[cfg(feature = "std")]
+pub use tree::tree;
+pub use wireguard::{
+    wg_cookie, wg_cookie_key, wg_mac, wg_mac1, wg_mac1_key, wg_mac2, wg_set_macs, wg_symmetric_state, wg_verify_mac1,
+    wg_verify_mac2, WG_CONSTRUCTION, WG_IDENTIFIER, WG_LABEL_COOKIE, WG_LABEL_MAC1,
+};
@@ -0,0 +1,300 @@
+//! Merkle trees over BLAKE2b in the shape of RFC 6962 and Sia: the left subtree of n leaves holds the largest power of
+//! two below n. Leaves and nodes are told apart by a prefix byte, 0x00 and 0x01, or by two personalizations.
+//! Verification of inclusion proofs and multi-proofs works in `no_std` without allocation.
+
+use crate::blake2b::{blake2b, blake2b_params};
+
+/// Errors of Merkle proofs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MerkleError {
+    /// A leaf index is past the end of the tree, or the indices of a multi-proof are not strictly increasing.
+    Index,
+    /// The proof has too few or too many hashes.
+    Length,
+    /// The proof leads to another root.
+    Root,
+}
+
+impl core::fmt::Display for MerkleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Self::Index => "invalid leaf index",
+            Self::Length => "invalid proof length",
+            Self::Root => "proof does not match the root",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for MerkleError {}
+
+/// Layouts of existing systems. They share the shape of the tree and the order of inclusion proofs: sibling hashes
+/// from the leaf up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MerkleLayout {
+    /// Certificate Transparency. The root of no leaves is the hash of the empty string.
+    Rfc6962,
+    /// Sia, with BLAKE2b-256. The root of no leaves is all zeros. A proof set of Sia's merkletree package holds the
+    /// leaf data in `proofSet[0]`, followed by the sibling hashes: verify it with `proofSet[0]` as the leaf and the
+    /// concatenation of `proofSet[1:]` as the path. The tests check this layout against the RFC 6962 model, whose
+    /// roots it shares but for the empty tree, and not against roots or proof sets produced by Sia.
+    Sia,
+}
+
+/// Hashing of a Merkle tree.
+#[derive(Clone)]
+pub struct Merkle {
+    l: MerkleLayout,
+    /// Digest byte length.
+    n: u8,
+    /// Personalizations of leaves and nodes, instead of prefix bytes.
+    p: Option<([u8; 16], [u8; 16])>,
+}
+
+impl Merkle {
+    /// Set the digest byte length, from 1 to 64.
+    pub fn digest(&mut self, n: u8) {
+        assert!((1..=64).contains(&n));
+        self.n = n;
+    }
+
+    /// Separate leaves and nodes by personalizations of at most 16 bytes instead of prefix bytes.
+    pub fn person(&mut self, leaf: &[u8], node: &[u8]) {
+        assert!(leaf.len() <= 16 && node.len() <= 16 && leaf != node);
+        let mut p = ([0; 16], [0; 16]);
+        p.0[..leaf.len()].copy_from_slice(leaf);
+        p.1[..node.len()].copy_from_slice(node);
+        self.p = Some(p);
+    }
+
+    fn hash(&self, person: Option<&[u8; 16]>, data: &[&[u8]]) -> [u8; 64] {
+        let mut p = blake2b_params();
+        p.digest(self.n);
+        if let Some(x) = person {
+            p.person(x);
+        }
+        let mut h = blake2b(p);
+        data.iter().for_each(|d| h.update(d));
+        let mut r = [0; 64];
+        h.digest(&mut r[..self.n as usize]);
+        r
+    }
+
+    fn leaf_hash(&self, data: &[u8]) -> [u8; 64] {
+        match &self.p {
+            Some((p, _)) => self.hash(Some(p), &[data]),
+            None => self.hash(None, &[&[0x00], data]),
+        }
+    }
+
+    fn node_hash(&self, left: &[u8], right: &[u8]) -> [u8; 64] {
+        let n = self.n as usize;
+        match &self.p {
+            Some((_, p)) => self.hash(Some(p), &[&left[..n], &right[..n]]),
+            None => self.hash(None, &[&[0x01], &left[..n], &right[..n]]),
+        }
+    }
+
+    /// Hash of a leaf.
+    pub fn leaf(&self, data: &[u8], out: &mut [u8]) {
+        out.copy_from_slice(&self.leaf_hash(data)[..self.n as usize]);
+    }
+
+    /// Hash of a node.
+    pub fn node(&self, left: &[u8], right: &[u8], out: &mut [u8]) {
+        assert!(left.len() == self.n as usize && right.len() == self.n as usize);
+        out.copy_from_slice(&self.node_hash(left, right)[..self.n as usize]);
+    }
+
+    /// Root of a tree without leaves.
+    pub fn empty(&self, out: &mut [u8]) {
+        match self.l {
+            MerkleLayout::Rfc6962 => out.copy_from_slice(&self.hash(None, &[])[..self.n as usize]),
+            MerkleLayout::Sia => out.copy_from_slice(&[0; 64][..self.n as usize]),
+        }
+    }
+
+    /// Verify the inclusion proof of leaf index of a tree of size leaves. The path is the concatenation of the sibling
+    /// hashes from the leaf up, as in RFC 9162, section 2.1.3.2.
+    pub fn verify(&self, root: &[u8], leaf: &[u8], index: u64, size: u64, path: &[u8]) -> Result<(), MerkleError> {
+        let n = self.n as usize;
+        if index >= size {
+            return Err(MerkleError::Index);
+        }
+        if !path.len().is_multiple_of(n) {
+            return Err(MerkleError::Length);
+        }
+        let (mut f, mut s) = (index, size - 1);
+        let mut r = self.leaf_hash(leaf);
+        for p in path.chunks(n) {
+            if s == 0 {
+                return Err(MerkleError::Length);
+            }
+            if f & 1 == 1 || f == s {
+                r = self.node_hash(p, &r);
+                while f & 1 == 0 && f != 0 {
+                    f >>= 1;
+                    s >>= 1;
+                }
+            } else {
+                r = self.node_hash(&r, p);
+            }
+            f >>= 1;
+            s >>= 1;
+        }
+        if s != 0 {
+            return Err(MerkleError::Length);
+        }
+        if r[..n] != *root {
+            return Err(MerkleError::Root);
+        }
+        Ok(())
+    }
+
+    /// Verify a multi-proof of several leaves, given with their strictly increasing indices. The proof is the
+    /// concatenation of the roots of the subtrees without any of the leaves, depth first and left to right.
+    pub fn verify_multi(
+        &self,
+        root: &[u8],
+        leaves: &[(u64, &[u8])],
+        size: u64,
+        proof: &[u8],
+    ) -> Result<(), MerkleError> {
+        if leaves.is_empty() || leaves.windows(2).any(|w| w[0].0 >= w[1].0) || leaves[leaves.len() - 1].0 >= size {
+            return Err(MerkleError::Index);
+        }
+        let mut proof = proof;
+        let r = self.multi(0, size, leaves, &mut proof)?;
+        if !proof.is_empty() {
+            return Err(MerkleError::Length);
+        }
+        if r[..self.n as usize] != *root {
+            return Err(MerkleError::Root);
+        }
+        Ok(())
+    }
+
+    /// Root of leaves lo..hi from the given leaves among them and the proof.
+    fn multi(&self, lo: u64, hi: u64, leaves: &[(u64, &[u8])], proof: &mut &[u8]) -> Result<[u8; 64], MerkleError> {
+        let n = self.n as usize;
+        if leaves.is_empty() {
+            if proof.len() < n {
+                return Err(MerkleError::Length);
+            }
+            let mut r = [0; 64];
+            r[..n].copy_from_slice(&proof[..n]);
+            *proof = &proof[n..];
+            return Ok(r);
+        }
+        if hi - lo == 1 {
+            return Ok(self.leaf_hash(leaves[0].1));
+        }
+        let m = lo + split(hi - lo);
+        let (a, b) = leaves.split_at(leaves.partition_point(|l| l.0 < m));
+        let x = self.multi(lo, m, a, proof)?;
+        let y = self.multi(m, hi, b, proof)?;
+        Ok(self.node_hash(&x, &y))
+    }
+
+    /// Build the tree of a list of leaves.
+    #[cfg(feature = "std")]
+    pub fn tree<T: AsRef<[u8]>>(&self, leaves: impl IntoIterator<Item = T>) -> MerkleTree {
+        let n = self.n as usize;
+        let mut l = std::vec::Vec::new();
+        leaves.into_iter().for_each(|d| l.extend_from_slice(&self.leaf_hash(d.as_ref())[..n]));
+        let mut levels = std::vec![l];
+        while levels[levels.len() - 1].len() > n {
+            let l = &levels[levels.len() - 1];
+            let mut u = std::vec::Vec::with_capacity(l.len() / 2 + n);
+            for c in l.chunks(2 * n) {
+                match c.len() == 2 * n {
+                    true => u.extend_from_slice(&self.node_hash(&c[..n], &c[n..])[..n]),
+                    false => u.extend_from_slice(c),
+                }
+            }
+            levels.push(u);
+        }
+        MerkleTree { m: self.clone(), levels }
+    }
+}
+
+/// Leaves of the left subtree of a tree of n > 1 leaves: the largest power of two below n.
+fn split(n: u64) -> u64 {
+    1 << (63 - (n - 1).leading_zeros())
+}
+
+/// A Merkle tree with the hashes of all its levels. An odd node at the end of a level moves up unchanged.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct MerkleTree {
+    m: Merkle,
+    /// Concatenated hashes of each level, leaves first.
+    levels: std::vec::Vec<std::vec::Vec<u8>>,
+}
+
+#[cfg(feature = "std")]
+impl MerkleTree {
+    fn get(&self, level: usize, i: u64) -> &[u8] {
+        let n = self.m.n as usize;
+        &self.levels[level][i as usize * n..(i as usize + 1) * n]
+    }
+
+    /// Number of leaves.
+    pub fn size(&self) -> u64 {
+        (self.levels[0].len() / self.m.n as usize) as u64
+    }
+
+    /// Root hash.
+    pub fn root(&self) -> std::vec::Vec<u8> {
+        let mut r = std::vec![0; self.m.n as usize];
+        match self.size() {
+            0 => self.m.empty(&mut r),
+            _ => r.copy_from_slice(self.get(self.levels.len() - 1, 0)),
+        }
+        r
+    }
+
+    /// Inclusion proof of a leaf.
+    pub fn proof(&self, index: u64) -> std::vec::Vec<u8> {
+        assert!(index < self.size());
+        let mut r = std::vec::Vec::new();
+        let mut i = index;
+        for (level, l) in self.levels[..self.levels.len() - 1].iter().enumerate() {
+            if ((i ^ 1) as usize) < l.len() / self.m.n as usize {
+                r.extend_from_slice(self.get(level, i ^ 1));
+            }
+            i >>= 1;
+        }
+        r
+    }
+
+    /// Multi-proof of several leaves, given by strictly increasing indices.
+    pub fn proof_multi(&self, indices: &[u64]) -> std::vec::Vec<u8> {
+        assert!(!indices.is_empty() && indices.windows(2).all(|w| w[0] < w[1]));
+        assert!(indices[indices.len() - 1] < self.size());
+        let mut r = std::vec::Vec::new();
+        self.multi(0, self.size(), indices, &mut r);
+        r
+    }
+
+    fn multi(&self, lo: u64, hi: u64, indices: &[u64], r: &mut std::vec::Vec<u8>) {
+        if indices.is_empty() {
+            // Leaves lo..hi are the node at the level of the smallest power of two holding them.
+            let level = (hi - lo).next_power_of_two().trailing_zeros() as usize;
+            r.extend_from_slice(self.get(level, lo >> level));
+            return;
+        }
+        if hi - lo == 1 {
+            return;
+        }
+        let m = lo + split(hi - lo);
+        let (a, b) = indices.split_at(indices.partition_point(|&i| i < m));
+        self.multi(lo, m, a, r);
+        self.multi(m, hi, b, r);
+    }
+}
+
+/// Create the hashing of a Merkle tree with a layout, of 32-byte digests and separated by prefix bytes.
+pub fn merkle(layout: MerkleLayout) -> Merkle {
+    Merkle { l: layout, n: 32, p: None }
+}
@@ -0,0 +1,347 @@
+//! Merkle Mountain Ranges as in Grin: an append-only list of perfect binary trees, stored in postorder. Every hash is
+//! BLAKE2b-256 of the 0-based position of its node as a big-endian u64, followed by the leaf data or the two child
+//! hashes. The root bags the peaks from right to left, committing to the size of the MMR.
+
+use crate::blake2b::{blake2b, blake2b_params};
+
+/// Most hashes in a proof, and most peaks.
+const MMR_PATH: usize = 64;
+
+/// Errors of Merkle Mountain Ranges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MmrError {
+    /// The size is not the size of an MMR.
+    Size,
+    /// The position is not a leaf of the MMR.
+    Position,
+    /// The backend does not hold the hash at a position, because it was pruned.
+    Missing(u64),
+    /// The backend failed to store hashes.
+    Backend,
+    /// The proof has more than 64 hashes.
+    Length,
+    /// The proof leads to another root.
+    Root,
+}
+
+impl core::fmt::Display for MmrError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Size => f.write_str("invalid MMR size"),
+            Self::Position => f.write_str("not a leaf position"),
+            Self::Missing(pos) => write!(f, "missing hash at position {}", pos),
+            Self::Backend => f.write_str("MMR backend failure"),
+            Self::Length => f.write_str("invalid proof length"),
+            Self::Root => f.write_str("proof does not match the root"),
+        }
+    }
+}
+
+impl core::error::Error for MmrError {}
+
+/// Hash of a leaf at a position.
+pub fn mmr_leaf_hash(pos: u64, leaf: &[u8]) -> [u8; 32] {
+    hash(pos, &[leaf])
+}
+
+/// Hash of a node at a position.
+pub fn mmr_node_hash(pos: u64, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hash(pos, &[left, right])
+}
+
+fn hash(pos: u64, data: &[&[u8]]) -> [u8; 32] {
+    let mut p = blake2b_params();
+    p.digest(32);
+    let mut h = blake2b(p);
+    h.update(&pos.to_be_bytes());
+    data.iter().for_each(|d| h.update(d));
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    r
+}
+
+/// Peaks of the MMR of a size as a bit map, and the height of the next node.
+fn peak_map_height(mut size: u64) -> (u64, u64) {
+    if size == 0 {
+        return (0, 0);
+    }
+    let mut peak_size = u64::MAX >> size.leading_zeros();
+    let mut peak_map = 0;
+    while peak_size != 0 {
+        peak_map <<= 1;
+        if size >= peak_size {
+            size -= peak_size;
+            peak_map |= 1;
+        }
+        peak_size >>= 1;
+    }
+    (peak_map, size)
+}
+
+/// Positions of the peaks of an MMR, from left to right.
+fn peaks(size: u64) -> impl DoubleEndedIterator<Item = u64> {
+    let (peak_map, _) = peak_map_height(size);
+    (0..64).rev().filter(move |h| peak_map >> h & 1 == 1).map(move |h| {
+        // Nodes of the higher peaks, then of this one.
+        let hi = peak_map >> h >> 1 << 1 << h;
+        2 * hi - hi.count_ones() as u64 + (2 << h) - 2
+    })
+}
+
+/// Parent and sibling of a position.
+fn family(pos: u64) -> (u64, u64) {
+    let (peak_map, height) = peak_map_height(pos);
+    let peak = 1 << height;
+    if peak_map & peak != 0 {
+        (pos + 1, pos + 1 - 2 * peak)
+    } else {
+        (pos + 2 * peak, pos + 2 * peak - 1)
+    }
+}
+
+/// Whether a position is the left child of its parent.
+fn is_left_sibling(pos: u64) -> bool {
+    let (peak_map, height) = peak_map_height(pos);
+    peak_map & (1 << height) == 0
+}
+
+/// Storage of the hashes of an MMR by position. A backend may drop the hashes of pruned subtrees, as long as it keeps
+/// the peaks and the siblings of the leaves still to be proven.
+pub trait MmrBackend {
+    /// Hash of the node at a position, or None if it is not stored.
+    fn get(&self, pos: u64) -> Option<[u8; 32]>;
+    /// Store the hashes of the next positions.
+    fn append(&mut self, hashes: &[[u8; 32]]) -> Result<(), MmrError>;
+}
+
+impl<B: MmrBackend> MmrBackend for &mut B {
+    fn get(&self, pos: u64) -> Option<[u8; 32]> {
+        (**self).get(pos)
+    }
+
+    fn append(&mut self, hashes: &[[u8; 32]]) -> Result<(), MmrError> {
+        (**self).append(hashes)
+    }
+}
+
+/// An inclusion proof: the sibling hashes up to the peak, the bagged peaks on its right, then the peaks on its left
+/// from the nearest one.
+#[derive(Clone)]
+pub struct MmrProof {
+    size: u64,
+    path: [[u8; 32]; MMR_PATH],
+    n: usize,
+}
+
+impl MmrProof {
+    /// A proof in an MMR of a size, with a path of at most 64 hashes.
+    pub fn new(size: u64, path: &[[u8; 32]]) -> Result<MmrProof, MmrError> {
+        if path.len() > MMR_PATH {
+            return Err(MmrError::Length);
+        }
+        let mut r = MmrProof { size, path: [[0; 32]; MMR_PATH], n: path.len() };
+        r.path[..path.len()].copy_from_slice(path);
+        Ok(r)
+    }
+
+    /// Size of the MMR.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Hashes of the path.
+    pub fn path(&self) -> &[[u8; 32]] {
+        &self.path[..self.n]
+    }
+
+    /// Verify that a leaf is at a position of the MMR with a root.
+    pub fn verify(&self, root: &[u8; 32], leaf: &[u8], pos: u64) -> Result<(), MmrError> {
+        if peak_map_height(self.size).1 != 0 {
+            return Err(MmrError::Size);
+        }
+        if pos >= self.size || peak_map_height(pos).1 != 0 {
+            return Err(MmrError::Position);
+        }
+        let last = peaks(self.size).next_back();
+        let mut pos = pos;
+        let mut h = mmr_leaf_hash(pos, leaf);
+        for s in self.path() {
+            let (parent, sibling) = family(pos);
+            let left = if peaks(self.size).any(|p| p == pos) {
+                Some(pos) == last
+            } else {
+                parent >= self.size || is_left_sibling(sibling)
+            };
+            h = match left {
+                true => mmr_node_hash(parent.min(self.size), s, &h),
+                false => mmr_node_hash(parent.min(self.size), &h, s),
+            };
+            pos = parent;
+        }
+        if h != *root {
+            return Err(MmrError::Root);
+        }
+        Ok(())
+    }
+}
+
+/// An append-only Merkle Mountain Range over a backend.
+pub struct Mmr<B> {
+    size: u64,
+    b: B,
+}
+
+impl<B: MmrBackend> Mmr<B> {
+    /// Number of nodes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Number of leaves.
+    pub fn leaves(&self) -> u64 {
+        peak_map_height(self.size).0
+    }
+
+    /// The backend.
+    pub fn backend(&self) -> &B {
+        &self.b
+    }
+
+    /// The backend, to prune it.
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.b
+    }
+
+    fn get(&self, pos: u64) -> Result<[u8; 32], MmrError> {
+        self.b.get(pos).ok_or(MmrError::Missing(pos))
+    }
+
+    /// Append a leaf and the parents it completes. Returns the position of the leaf.
+    pub fn push(&mut self, leaf: &[u8]) -> Result<u64, MmrError> {
+        let (peak_map, _) = peak_map_height(self.size);
+        let mut pos = self.size;
+        let mut hashes = [[0; 32]; MMR_PATH];
+        hashes[0] = mmr_leaf_hash(pos, leaf);
+        let mut n = 1;
+        let mut peak = 1;
+        while peak_map & peak != 0 {
+            let left = self.get(pos + 1 - 2 * peak)?;
+            peak *= 2;
+            pos += 1;
+            hashes[n] = mmr_node_hash(pos, &left, &hashes[n - 1]);
+            n += 1;
+        }
+        self.b.append(&hashes[..n])?;
+        let r = self.size;
+        self.size = pos + 1;
+        Ok(r)
+    }
+
+    /// Bag the peaks from a position on, from right to left.
+    fn bag(&self, from: u64) -> Result<Option<[u8; 32]>, MmrError> {
+        let mut r = None;
+        for p in peaks(self.size).rev().take_while(|&p| p >= from) {
+            let h = self.get(p)?;
+            r = Some(match r {
+                None => h,
+                Some(x) => mmr_node_hash(self.size, &h, &x),
+            });
+        }
+        Ok(r)
+    }
+
+    /// Root hash, all zeros for an empty MMR.
+    pub fn root(&self) -> Result<[u8; 32], MmrError> {
+        Ok(self.bag(0)?.unwrap_or([0; 32]))
+    }
+
+    /// Inclusion proof of the leaf at a position.
+    pub fn proof(&self, pos: u64) -> Result<MmrProof, MmrError> {
+        if pos >= self.size || peak_map_height(pos).1 != 0 {
+            return Err(MmrError::Position);
+        }
+        let mut r = MmrProof { size: self.size, path: [[0; 32]; MMR_PATH], n: 0 };
+        let mut p = pos;
+        loop {
+            let (parent, sibling) = family(p);
+            if parent >= self.size {
+                break;
+            }
+            r.path[r.n] = self.get(sibling)?;
+            r.n += 1;
+            p = parent;
+        }
+        if let Some(h) = self.bag(p + 1)? {
+            r.path[r.n] = h;
+            r.n += 1;
+        }
+        for q in peaks(self.size).rev().filter(|&q| q < p) {
+            r.path[r.n] = self.get(q)?;
+            r.n += 1;
+        }
+        Ok(r)
+    }
+}
+
+/// Open an MMR of a size over a backend holding its hashes.
+pub fn mmr<B: MmrBackend>(backend: B, size: u64) -> Result<Mmr<B>, MmrError> {
+    if peak_map_height(size).1 != 0 {
+        return Err(MmrError::Size);
+    }
+    Ok(Mmr { size, b: backend })
+}
+
+/// A backend in memory. Pruning a leaf drops the hashes of the subtrees whose leaves are all pruned, except their root.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct MmrMemory {
+    /// Hash of each position, None once dropped.
+    h: std::vec::Vec<Option<[u8; 32]>>,
+    /// Whether all leaves under each position are pruned.
+    p: std::vec::Vec<bool>,
+}
+
+#[cfg(feature = "std")]
+impl MmrMemory {
+    /// Prune the leaf at a position. Its hash is dropped with the one of its sibling once the sibling is pruned too.
+    pub fn prune(&mut self, pos: u64) -> Result<(), MmrError> {
+        if pos >= self.h.len() as u64 || peak_map_height(pos).1 != 0 {
+            return Err(MmrError::Position);
+        }
+        let mut pos = pos;
+        self.p[pos as usize] = true;
+        loop {
+            let (parent, sibling) = family(pos);
+            if parent >= self.h.len() as u64 || !self.p[sibling as usize] {
+                return Ok(());
+            }
+            self.h[pos as usize] = None;
+            self.h[sibling as usize] = None;
+            self.p[parent as usize] = true;
+            pos = parent;
+        }
+    }
+
+    /// Number of hashes held.
+    pub fn len(&self) -> usize {
+        self.h.iter().filter(|h| h.is_some()).count()
+    }
+
+    /// Whether no hashes are held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl MmrBackend for MmrMemory {
+    fn get(&self, pos: u64) -> Option<[u8; 32]> {
+        self.h.get(pos as usize).copied().flatten()
+    }
+
+    fn append(&mut self, hashes: &[[u8; 32]]) -> Result<(), MmrError> {
+        self.h.extend(hashes.iter().map(|&h| Some(h)));
+        self.p.resize(self.h.len(), false);
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+//! Hash functions and the symmetric state of the Noise Protocol Framework (revision 34, sections 4.3 and 5.2) for the
+//! BLAKE2s and BLAKE2b hash names. The cipher is left to the caller: the state hands out the current cipher key, and
+//! EncryptAndHash and DecryptAndHash are mix_hash() of the ciphertext.
+
+use crate::blake2b::{blake2b, blake2b_params};
+use crate::blake2s::{blake2s, blake2s_params};
+use crate::hmac::{hmac_blake2b, hmac_blake2s};
+
+/// Cipher key bytes. Outputs of HKDF longer than this are truncated, as in MixKey and Split.
+const NOISE_KEY: usize = 32;
+
+/// HASH() of the BLAKE2s hash name: unkeyed BLAKE2s-256 over the concatenation of the inputs.
+pub fn noise_hash_blake2s(data: &[&[u8]]) -> [u8; 32] {
+    let mut p = blake2s_params();
+    p.digest(32);
+    let mut h = blake2s(p);
+    data.iter().for_each(|d| h.update(d));
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    r
+}
+
+/// HASH() of the BLAKE2b hash name: unkeyed BLAKE2b-512 over the concatenation of the inputs.
+pub fn noise_hash_blake2b(data: &[&[u8]]) -> [u8; 64] {
+    let mut p = blake2b_params();
+    p.digest(64);
+    let mut h = blake2b(p);
+    data.iter().for_each(|d| h.update(d));
+    let mut r = [0; 64];
+    h.digest(&mut r);
+    r
+}
+
+/// HMAC-HASH() of the BLAKE2s hash name over the concatenation of the inputs.
+pub fn noise_hmac_blake2s(key: &[u8], data: &[&[u8]]) -> [u8; 32] {
+    let mut h = hmac_blake2s(key);
+    data.iter().for_each(|d| h.update(d));
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    r
+}
+
+/// HMAC-HASH() of the BLAKE2b hash name over the concatenation of the inputs.
+pub fn noise_hmac_blake2b(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut h = hmac_blake2b(key);
+    data.iter().for_each(|d| h.update(d));
+    let mut r = [0; 64];
+    h.digest(&mut r);
+    r
+}
+
+/// HKDF() of the BLAKE2s hash name with two outputs.
+pub fn noise_hkdf2_blake2s(ck: &[u8], ikm: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let t = noise_hmac_blake2s(ck, &[ikm]);
+    let a = noise_hmac_blake2s(&t, &[&[1]]);
+    let b = noise_hmac_blake2s(&t, &[&a, &[2]]);
+    (a, b)
+}
+
+/// HKDF() of the BLAKE2s hash name with three outputs.
+pub fn noise_hkdf3_blake2s(ck: &[u8], ikm: &[u8]) -> ([u8; 32], [u8; 32], [u8; 32]) {
+    let t = noise_hmac_blake2s(ck, &[ikm]);
+    let a = noise_hmac_blake2s(&t, &[&[1]]);
+    let b = noise_hmac_blake2s(&t, &[&a, &[2]]);
+    let c = noise_hmac_blake2s(&t, &[&b, &[3]]);
+    (a, b, c)
+}
+
+/// HKDF() of the BLAKE2b hash name with two outputs.
+pub fn noise_hkdf2_blake2b(ck: &[u8], ikm: &[u8]) -> ([u8; 64], [u8; 64]) {
+    let t = noise_hmac_blake2b(ck, &[ikm]);
+    let a = noise_hmac_blake2b(&t, &[&[1]]);
+    let b = noise_hmac_blake2b(&t, &[&a, &[2]]);
+    (a, b)
+}
+
+/// HKDF() of the BLAKE2b hash name with three outputs.
+pub fn noise_hkdf3_blake2b(ck: &[u8], ikm: &[u8]) -> ([u8; 64], [u8; 64], [u8; 64]) {
+    let t = noise_hmac_blake2b(ck, &[ikm]);
+    let a = noise_hmac_blake2b(&t, &[&[1]]);
+    let b = noise_hmac_blake2b(&t, &[&a, &[2]]);
+    let c = noise_hmac_blake2b(&t, &[&b, &[3]]);
+    (a, b, c)
+}
+
+fn key(b: &[u8]) -> [u8; NOISE_KEY] {
+    let mut r = [0; NOISE_KEY];
+    r.copy_from_slice(&b[..NOISE_KEY]);
+    r
+}
+
+/// SymmetricState of Noise with BLAKE2s, without the CipherState: the cipher key is exposed instead.
+#[derive(Clone)]
+pub struct SymmetricState2s {
+    /// Chaining key.
+    ck: [u8; 32],
+    /// Handshake hash.
+    h: [u8; 32],
+    /// Cipher key, None until the first mix_key().
+    k: Option<[u8; NOISE_KEY]>,
+}
+
+impl SymmetricState2s {
+    pub const HASHLEN: usize = 32;
+    pub const BLOCKLEN: usize = 64;
+
+    /// InitializeSymmetric(protocol_name).
+    pub fn new(protocol_name: &[u8]) -> Self {
+        let mut h = [0; 32];
+        if protocol_name.len() <= Self::HASHLEN {
+            h[..protocol_name.len()].copy_from_slice(protocol_name);
+        } else {
+            h = noise_hash_blake2s(&[protocol_name]);
+        }
+        Self { ck: h, h, k: None }
+    }
+
+    /// MixKey(input_key_material).
+    pub fn mix_key(&mut self, ikm: &[u8]) {
+        let (ck, k) = noise_hkdf2_blake2s(&self.ck, ikm);
+        self.ck = ck;
+        self.k = Some(key(&k));
+    }
+
+    /// MixHash(data).
+    pub fn mix_hash(&mut self, data: &[u8]) {
+        self.h = noise_hash_blake2s(&[&self.h, data]);
+    }
+
+    /// MixKeyAndHash(input_key_material), used for pre-shared keys.
+    pub fn mix_key_and_hash(&mut self, ikm: &[u8]) {
+        let (ck, h, k) = noise_hkdf3_blake2s(&self.ck, ikm);
+        self.ck = ck;
+        self.mix_hash(&h);
+        self.k = Some(key(&k));
+    }
+
+    /// GetHandshakeHash().
+    pub fn handshake_hash(&self) -> &[u8; 32] {
+        &self.h
+    }
+
+    /// The key of the CipherState, None before any key was mixed in.
+    pub fn cipher_key(&self) -> Option<&[u8; NOISE_KEY]> {
+        self.k.as_ref()
+    }
+
+    /// Split(). Returns the cipher keys of the initiator to responder and responder to initiator directions.
+    pub fn split(&self) -> ([u8; NOISE_KEY], [u8; NOISE_KEY]) {
+        let (a, b) = noise_hkdf2_blake2s(&self.ck, &[]);
+        (key(&a), key(&b))
+    }
+}
+
+/// SymmetricState of Noise with BLAKE2b, without the CipherState: the cipher key is exposed instead.
+#[derive(Clone)]
+pub struct SymmetricState2b {
+    /// Chaining key.
+    ck: [u8; 64],
+    /// Handshake hash.
+    h: [u8; 64],
+    /// Cipher key, None until the first mix_key().
+    k: Option<[u8; NOISE_KEY]>,
+}
+
+impl SymmetricState2b {
+    pub const HASHLEN: usize = 64;
+    pub const BLOCKLEN: usize = 128;
+
+    /// InitializeSymmetric(protocol_name).
+    pub fn new(protocol_name: &[u8]) -> Self {
+        let mut h = [0; 64];
+        if protocol_name.len() <= Self::HASHLEN {
+            h[..protocol_name.len()].copy_from_slice(protocol_name);
+        } else {
+            h = noise_hash_blake2b(&[protocol_name]);
+        }
+        Self { ck: h, h, k: None }
+    }
+
+    /// MixKey(input_key_material). The cipher key is the first 32 bytes of the HKDF output.
+    pub fn mix_key(&mut self, ikm: &[u8]) {
+        let (ck, k) = noise_hkdf2_blake2b(&self.ck, ikm);
+        self.ck = ck;
+        self.k = Some(key(&k));
+    }
+
+    /// MixHash(data).
+    pub fn mix_hash(&mut self, data: &[u8]) {
+        self.h = noise_hash_blake2b(&[&self.h, data]);
+    }
+
+    /// MixKeyAndHash(input_key_material), used for pre-shared keys.
+    pub fn mix_key_and_hash(&mut self, ikm: &[u8]) {
+        let (ck, h, k) = noise_hkdf3_blake2b(&self.ck, ikm);
+        self.ck = ck;
+        self.mix_hash(&h);
+        self.k = Some(key(&k));
+    }
+
+    /// GetHandshakeHash().
+    pub fn handshake_hash(&self) -> &[u8; 64] {
+        &self.h
+    }
+
+    /// The key of the CipherState, None before any key was mixed in.
+    pub fn cipher_key(&self) -> Option<&[u8; NOISE_KEY]> {
+        self.k.as_ref()
+    }
+
+    /// Split(). Returns the cipher keys of the initiator to responder and responder to initiator directions, the first
+    /// 32 bytes of each HKDF output.
+    pub fn split(&self) -> ([u8; NOISE_KEY], [u8; NOISE_KEY]) {
+        let (a, b) = noise_hkdf2_blake2b(&self.ck, &[]);
+        (key(&a), key(&b))
+    }
+}
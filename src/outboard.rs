@@ -0,0 +1,353 @@
+//! Verified streaming over the tree mode of BLAKE2b. The input is cut into leaves of a fixed length, hashed as a binary
+//! tree with fanout 2 and unlimited depth: the node at depth d and offset j hashes the chaining values of the nodes 2j
+//! and 2j + 1 of depth d - 1, and the last node of each depth is flagged. An outboard file holds the 64-byte chaining
+//! values of every node but the root in post-order, each node after its children, followed by the input length as a
+//! little-endian u64. It is written as the input is read, with two leaves and one chaining value per depth in memory.
+//! Any byte range of the input can then be read and checked against the root alone.
+
+use crate::blake2b::{blake2b, blake2b_params, Blake2b};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::vec;
+use std::vec::Vec;
+
+/// Chaining value bytes.
+const OUTBOARD_CV: usize = 64;
+/// Trailer bytes.
+const OUTBOARD_TRAILER: u64 = 8;
+
+/// Errors of verified streaming, carried by `std::io::Error` of kind `InvalidData`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutboardError {
+    /// The chaining values below the root, or the only leaf, do not match the root hash.
+    Root,
+    /// The chaining values below the node at a depth and offset do not match it.
+    Node(u8, u64),
+    /// The leaf at an offset does not match its chaining value.
+    Leaf(u64),
+    /// The outboard file does not have the size of the tree of the input length it ends with.
+    Length,
+    /// The input or the outboard file ends too early.
+    Truncated,
+}
+
+impl core::fmt::Display for OutboardError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Root => f.write_str("outboard does not match the root hash"),
+            Self::Node(d, j) => write!(f, "outboard does not match the node at depth {} offset {}", d, j),
+            Self::Leaf(j) => write!(f, "input does not match the leaf at offset {}", j),
+            Self::Length => f.write_str("outboard does not match its input length"),
+            Self::Truncated => f.write_str("input or outboard truncated"),
+        }
+    }
+}
+
+impl core::error::Error for OutboardError {}
+
+impl From<OutboardError> for std::io::Error {
+    fn from(e: OutboardError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Read exactly enough bytes to fill the buffer, reporting an early end as truncation.
+fn read_exact(r: &mut impl Read, b: &mut [u8]) -> std::io::Result<()> {
+    r.read_exact(b).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => OutboardError::Truncated.into(),
+        _ => e,
+    })
+}
+
+/// Read until the buffer is full or the input ends. Returns the number of bytes read.
+fn fill(r: &mut impl Read, b: &mut [u8]) -> std::io::Result<usize> {
+    let mut n = 0;
+    while n < b.len() {
+        match r.read(&mut b[n..]) {
+            Ok(0) => break,
+            Ok(m) => n += m,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(n)
+}
+
+/// Options of the tree.
+#[derive(Clone)]
+pub struct Outboard {
+    /// Leaf byte length.
+    l: u32,
+    /// Root digest byte length.
+    n: u8,
+}
+
+impl Outboard {
+    /// Set the leaf byte length.
+    pub fn leaf_length(&mut self, n: u32) {
+        assert!(n >= 1);
+        self.l = n;
+    }
+
+    /// Set the root digest byte length, from 1 to 64.
+    pub fn digest(&mut self, n: u8) {
+        assert!((1..=64).contains(&n));
+        self.n = n;
+    }
+
+    /// Hash object of the node at a depth and offset, the last one of its depth or not.
+    fn node(&self, depth: u8, offset: u64, last: bool) -> Blake2b {
+        let mut p = blake2b_params();
+        p.digest(self.n);
+        p.fanout(2);
+        p.depth(255);
+        p.leaf_length(self.l);
+        p.node_offset(offset);
+        p.node_depth(depth);
+        p.inner_length(OUTBOARD_CV as u8);
+        let mut h = blake2b(p);
+        if last {
+            h.last_node();
+        }
+        h
+    }
+
+    /// Number of nodes at each depth for an input length, from the leaves up to the root.
+    fn shape(&self, len: u64) -> Vec<u64> {
+        let mut r = vec![len.div_ceil(self.l as u64).max(1)];
+        while r[r.len() - 1] > 1 {
+            r.push(r[r.len() - 1].div_ceil(2));
+        }
+        r
+    }
+
+    /// Hash all the data from a reader and write the outboard file, in post-order as the leaves are read. Returns the
+    /// root hash.
+    pub fn encode(&self, mut r: impl Read, mut w: impl Write) -> std::io::Result<Vec<u8>> {
+        let mut len = 0;
+        let mut cur = vec![0; self.l as usize];
+        let mut next = vec![0; self.l as usize];
+        let mut n = fill(&mut r, &mut cur)?;
+        // Nodes waiting for their right sibling, by increasing offset and decreasing depth.
+        let mut left: Vec<(u8, [u8; OUTBOARD_CV])> = Vec::new();
+        let mut i = 0;
+        loop {
+            len += n as u64;
+            // A leaf is the last one when the input ends right after it.
+            let m = if n == cur.len() { fill(&mut r, &mut next)? } else { 0 };
+            let last = m == 0;
+            let mut h = self.node(0, i, last);
+            h.update(&cur[..n]);
+            let mut cv = h.finalize();
+            // Go up while this node completes its parent, and after the last leaf up to the root.
+            let (mut d, mut j) = (0, i);
+            loop {
+                if last && left.is_empty() {
+                    w.write_all(&len.to_le_bytes())?;
+                    return Ok(cv[..self.n as usize].to_vec());
+                }
+                w.write_all(&cv)?;
+                let sibling = match left.last() {
+                    Some(x) if x.0 == d => left.pop(),
+                    _ if !last => {
+                        left.push((d, cv));
+                        break;
+                    }
+                    _ => None,
+                };
+                let mut h = self.node(d + 1, j >> 1, last);
+                if let Some((_, x)) = sibling {
+                    h.update(&x);
+                }
+                h.update(&cv);
+                cv = h.finalize();
+                d += 1;
+                j >>= 1;
+            }
+            core::mem::swap(&mut cur, &mut next);
+            n = m;
+            i += 1;
+        }
+    }
+
+    /// Open the input and its outboard file for verified reading against a trusted root hash. The path to the last
+    /// leaf is checked right away, so that the input length is trusted.
+    pub fn reader<R: Read + Seek, O: Read + Seek>(
+        &self,
+        root: &[u8],
+        data: R,
+        mut outboard: O,
+    ) -> std::io::Result<OutboardReader<R, O>> {
+        assert_eq!(root.len(), self.n as usize);
+        let end = outboard.seek(SeekFrom::End(0))?;
+        if end < OUTBOARD_TRAILER {
+            return Err(OutboardError::Truncated.into());
+        }
+        let mut b = [0; OUTBOARD_TRAILER as usize];
+        outboard.seek(SeekFrom::Start(end - OUTBOARD_TRAILER))?;
+        read_exact(&mut outboard, &mut b)?;
+        let len = u64::from_le_bytes(b);
+        let shape = self.shape(len);
+        // A forged length may not fit: the sizes saturate and do not match.
+        let nodes = shape.iter().fold(0u64, |r, &x| r.saturating_add(x)) - 1;
+        if nodes.saturating_mul(OUTBOARD_CV as u64).saturating_add(OUTBOARD_TRAILER) != end {
+            return Err(OutboardError::Length.into());
+        }
+        let mut r = OutboardReader {
+            o: self.clone(),
+            root: root.to_vec(),
+            data,
+            outboard,
+            len,
+            shape,
+            cache: Vec::new(),
+            leaf: None,
+            buf: Vec::new(),
+            pos: 0,
+        };
+        r.load(r.shape[0] - 1)?;
+        Ok(r)
+    }
+}
+
+/// A reader of an input that checks every leaf against the root hash before returning its bytes. Errors of kind
+/// `InvalidData` carry an `OutboardError` naming the node that does not match.
+pub struct OutboardReader<R, O> {
+    o: Outboard,
+    root: Vec<u8>,
+    data: R,
+    outboard: O,
+    /// Input byte length.
+    len: u64,
+    /// Number of nodes at each depth.
+    shape: Vec<u64>,
+    /// Verified chaining values: depth, offset and value of the nodes on the path to the last leaf read and their siblings.
+    cache: Vec<(u8, u64, [u8; OUTBOARD_CV])>,
+    /// Offset of the leaf in the buffer.
+    leaf: Option<u64>,
+    buf: Vec<u8>,
+    /// Read position.
+    pos: u64,
+}
+
+impl<R: Read + Seek, O: Read + Seek> OutboardReader<R, O> {
+    /// Input byte length.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the input is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Offset in the outboard file of the chaining value of the node at a depth and offset, below the root. The nodes
+    /// before it are the ones wholly on the left of its leaves, and its descendants.
+    fn position(&self, d: u8, j: u64) -> u64 {
+        let first = j << d;
+        let mut r = 0u64;
+        for (e, &n) in self.shape.iter().enumerate() {
+            r = r.saturating_add(first >> e);
+            if e < d as usize {
+                let k = j << (d as usize - e);
+                r = r.saturating_add((n - k).min(1 << (d as usize - e)));
+            }
+        }
+        r.saturating_mul(OUTBOARD_CV as u64)
+    }
+
+    /// Check the children of the node at a depth and offset against its chaining value, or the root hash, and cache
+    /// them.
+    fn check(&mut self, d: u8, j: u64, cv: &[u8]) -> std::io::Result<()> {
+        let k = (self.shape[d as usize - 1] - 2 * j).min(2);
+        let mut b = [0; 2 * OUTBOARD_CV];
+        for (c, x) in b.chunks_mut(OUTBOARD_CV).take(k as usize).enumerate() {
+            self.outboard.seek(SeekFrom::Start(self.position(d - 1, 2 * j + c as u64)))?;
+            read_exact(&mut self.outboard, x)?;
+        }
+        let mut h = self.o.node(d, j, j == self.shape[d as usize] - 1);
+        h.update(&b[..k as usize * OUTBOARD_CV]);
+        if h.finalize()[..cv.len()] != *cv {
+            return Err(match d as usize == self.shape.len() - 1 {
+                true => OutboardError::Root.into(),
+                false => OutboardError::Node(d, j).into(),
+            });
+        }
+        for (c, x) in b[..k as usize * OUTBOARD_CV].chunks(OUTBOARD_CV).enumerate() {
+            self.cache.push((d - 1, 2 * j + c as u64, x.try_into().unwrap()));
+        }
+        Ok(())
+    }
+
+    /// Read and check the leaf at an offset into the buffer.
+    fn load(&mut self, i: u64) -> std::io::Result<()> {
+        if self.leaf == Some(i) {
+            return Ok(());
+        }
+        let top = self.shape.len() - 1;
+        // Keep the verified nodes that are ancestors of this leaf, and go down from the lowest one.
+        self.cache.retain(|&(d, j, _)| j == i >> d);
+        let mut cv = match self.cache.iter().min_by_key(|x| x.0) {
+            Some(&(d, _, cv)) => (d, cv.to_vec()),
+            None => (top as u8, self.root.clone()),
+        };
+        while cv.0 > 0 {
+            let d = cv.0;
+            self.check(d, i >> d, &cv.1)?;
+            let x = self.cache.iter().find(|x| x.0 == d - 1 && x.1 == i >> (d - 1)).unwrap();
+            cv = (d - 1, x.2.to_vec());
+        }
+        let l = self.o.l as u64;
+        let n = (self.len - i * l).min(l) as usize;
+        self.buf.resize(n, 0);
+        self.data.seek(SeekFrom::Start(i * l))?;
+        read_exact(&mut self.data, &mut self.buf)?;
+        let mut h = self.o.node(0, i, i == self.shape[0] - 1);
+        h.update(&self.buf);
+        if h.finalize()[..cv.1.len()] != *cv.1 {
+            self.leaf = None;
+            return Err(match top {
+                0 => OutboardError::Root.into(),
+                _ => OutboardError::Leaf(i).into(),
+            });
+        }
+        self.leaf = Some(i);
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek, O: Read + Seek> Read for OutboardReader<R, O> {
+    fn read(&mut self, b: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos >= self.len || b.is_empty() {
+            return Ok(0);
+        }
+        let l = self.o.l as u64;
+        self.load(self.pos / l)?;
+        let s = (self.pos % l) as usize;
+        let n = (self.buf.len() - s).min(b.len());
+        b[..n].copy_from_slice(&self.buf[s..s + n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read + Seek, O: Read + Seek> Seek for OutboardReader<R, O> {
+    fn seek(&mut self, p: SeekFrom) -> std::io::Result<u64> {
+        let p = match p {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(n) => self.len.checked_add_signed(n),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n),
+        };
+        match p {
+            Some(p) => {
+                self.pos = p;
+                Ok(p)
+            }
+            None => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid seek to a negative position")),
+        }
+    }
+}
+
+/// Create the options of verified streaming, with leaves of 16 KiB and a 32-byte root hash.
+pub fn outboard() -> Outboard {
+    Outboard { l: 1 << 14, n: 32 }
+}
@@ -0,0 +1,114 @@
+//! Hash a single large input with BLAKE2bp or BLAKE2sp on multiple threads. The digests are identical to those of the
+//! single-threaded Blake2bp and Blake2sp hash objects.
+
+use crate::blake2bp::Blake2bp;
+use crate::blake2sp::Blake2sp;
+use crate::pow::Pow;
+use std::io::Read;
+use std::vec;
+
+/// Options for hashing on multiple threads.
+pub struct Parallel {
+    /// Thread pool, or None for the global rayon pool.
+    p: Option<rayon::ThreadPool>,
+    /// Inputs shorter than this are hashed on the calling thread.
+    t: usize,
+}
+
+impl Parallel {
+    /// Set the number of threads. 0 for the global rayon pool. At most 4 threads are busy with BLAKE2bp, and 8 with
+    /// BLAKE2sp. Fails when the threads cannot be spawned, leaving the previous pool in place.
+    pub fn threads(&mut self, n: usize) -> Result<(), rayon::ThreadPoolBuildError> {
+        self.p = match n {
+            0 => None,
+            n => Some(rayon::ThreadPoolBuilder::new().num_threads(n).build()?),
+        };
+        Ok(())
+    }
+
+    /// Set the byte length below which inputs are hashed on the calling thread.
+    pub fn threshold(&mut self, n: usize) {
+        self.t = n;
+    }
+
+    fn install(&self, f: impl FnOnce() + Send) {
+        match &self.p {
+            Some(p) => p.install(f),
+            None => f(),
+        }
+    }
+
+    /// Update a BLAKE2bp hash object's state with the provided data.
+    pub fn update_2bp(&self, h: &mut Blake2bp, data: &[u8]) {
+        if data.len() < self.t {
+            h.update(data);
+        } else {
+            self.install(|| h.update_rayon(data));
+        }
+    }
+
+    /// Update a BLAKE2sp hash object's state with the provided data.
+    pub fn update_2sp(&self, h: &mut Blake2sp, data: &[u8]) {
+        if data.len() < self.t {
+            h.update(data);
+        } else {
+            self.install(|| h.update_rayon(data));
+        }
+    }
+
+    /// Update a BLAKE2bp hash object's state with all the data from a reader, such as a file.
+    pub fn read_2bp(&self, h: &mut Blake2bp, r: impl Read) -> std::io::Result<()> {
+        read_chunks(r, |data| self.update_2bp(h, data))
+    }
+
+    /// Update a BLAKE2sp hash object's state with all the data from a reader, such as a file.
+    pub fn read_2sp(&self, h: &mut Blake2sp, r: impl Read) -> std::io::Result<()> {
+        read_chunks(r, |data| self.update_2sp(h, data))
+    }
+
+    /// Search for a proof-of-work nonce like `Pow::search`, with ranges of nonces tried on the threads of the pool. The
+    /// result is the same as on a single thread: the first solution in the order of the search.
+    pub fn search(&self, w: &Pow, threshold: u64, start: u64, count: u64) -> Option<u64> {
+        use rayon::prelude::*;
+        let mut r = None;
+        self.install(|| {
+            r = (0..count.div_ceil(NONCES)).into_par_iter().find_map_first(|c| {
+                let i = c * NONCES;
+                w.search(threshold, start.wrapping_add(i), NONCES.min(count - i))
+            });
+        });
+        r
+    }
+}
+
+/// Nonces tried by a thread at a time when searching.
+const NONCES: u64 = 1 << 16;
+
+/// Chunk size used when reading. Large enough that each leaf gets a good amount of work per chunk.
+const CHUNK: usize = 1 << 22;
+
+/// Read all the data from a reader, in chunks of the chunk size except for the last one.
+fn read_chunks(mut r: impl Read, mut f: impl FnMut(&[u8])) -> std::io::Result<()> {
+    let mut b = vec![0; CHUNK];
+    loop {
+        let mut n = 0;
+        while n < b.len() {
+            match r.read(&mut b[n..]) {
+                Ok(0) => break,
+                Ok(m) => n += m,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        f(&b[..n]);
+        if n < b.len() {
+            return Ok(());
+        }
+    }
+}
+
+/// Create the options for hashing on multiple threads. By default the global rayon pool is used, and inputs shorter
+/// than 64 KiB are hashed on the calling thread.
+pub fn parallel() -> Parallel {
+    Parallel { p: None, t: 1 << 16 }
+}
@@ -0,0 +1,105 @@
+//! Proof-of-work over BLAKE2b of a fixed prefix, an 8-byte little-endian nonce and a fixed suffix, as in Nano where the
+//! work value is BLAKE2b-64 of the nonce and the block root. The blocks before the nonce are absorbed once.
+
+use crate::blake2b::{blake2b, blake2b_compress, Param2b};
+
+/// Block bytes.
+const POW_BB: usize = 128;
+/// Nonce bytes.
+const POW_NONCE: usize = 8;
+
+/// Midstate of a proof-of-work: the state after the blocks before the nonce, and the remaining one or two blocks with
+/// a hole for the nonce.
+#[derive(Clone)]
+pub struct Pow {
+    /// Internal state of the hash after the fixed blocks.
+    h: [u64; 8],
+    /// Message byte offset before the remaining blocks.
+    t: u128,
+    /// Remaining blocks as words, with zeros in place of the nonce.
+    m: [u64; 32],
+    /// Remaining bytes.
+    l: usize,
+    /// Nonce offset in the remaining blocks.
+    o: usize,
+    /// Digest byte length.
+    n: u8,
+}
+
+impl Pow {
+    fn offset(&self, n: usize) -> [u64; 2] {
+        let t = self.t + n as u128;
+        [t as u64, (t >> 64) as u64]
+    }
+
+    /// Final state for a nonce.
+    fn state(&self, nonce: u64) -> [u64; 8] {
+        let mut m = self.m;
+        let (i, s) = (self.o / 8, self.o % 8 * 8);
+        m[i] |= nonce << s;
+        if s != 0 {
+            m[i + 1] |= nonce >> (64 - s);
+        }
+        let (m0, m1) = m.split_at(16);
+        let mut h = self.h;
+        if self.l > POW_BB {
+            blake2b_compress(&mut h, m0.try_into().unwrap(), &self.offset(POW_BB), &[0, 0], 12);
+            blake2b_compress(&mut h, m1.try_into().unwrap(), &self.offset(self.l), &[u64::MAX, 0], 12);
+        } else {
+            blake2b_compress(&mut h, m0.try_into().unwrap(), &self.offset(self.l), &[u64::MAX, 0], 12);
+        }
+        h
+    }
+
+    /// The digest of a nonce.
+    pub fn digest(&self, nonce: u64, d: &mut [u8]) {
+        assert_eq!(d.len(), self.n as usize);
+        let h = self.state(nonce);
+        for (i, x) in d.iter_mut().enumerate() {
+            *x = (h[i / 8] >> (8 * (i % 8))) as u8;
+        }
+    }
+
+    /// The work value of a nonce: the first 8 bytes of its digest as a little-endian integer.
+    pub fn work(&self, nonce: u64) -> u64 {
+        self.state(nonce)[0]
+    }
+
+    /// Whether the work value of a nonce reaches the threshold.
+    pub fn check(&self, nonce: u64, threshold: u64) -> bool {
+        self.work(nonce) >= threshold
+    }
+
+    /// Try count nonces from start, wrapping around, and return the first one whose work value reaches the threshold.
+    pub fn search(&self, threshold: u64, start: u64, count: u64) -> Option<u64> {
+        (0..count).map(|i| start.wrapping_add(i)).find(|&nonce| self.check(nonce, threshold))
+    }
+}
+
+/// Create a proof-of-work over BLAKE2b with a parameter block of at least 8 digest bytes. The suffix is at most 120
+/// bytes.
+pub fn pow(param2b: Param2b, prefix: &[u8], suffix: &[u8]) -> Pow {
+    assert!(suffix.len() <= POW_BB - POW_NONCE);
+    let mut s = blake2b(param2b);
+    s.update(prefix);
+    let (mut h, t, buf, n) = s.midstate();
+    assert!(n >= 8);
+    let mut t = t[0] as u128 | (t[1] as u128) << 64;
+    let mut b = [0; 2 * POW_BB];
+    b[..buf.len()].copy_from_slice(buf);
+    let mut o = buf.len();
+    let mut l = o + POW_NONCE + suffix.len();
+    b[o + POW_NONCE..l].copy_from_slice(suffix);
+    // A full block before the nonce is not the last one and can be absorbed now.
+    if o >= POW_BB {
+        t += POW_BB as u128;
+        let m = core::array::from_fn(|k| u64::from_le_bytes(b[k * 8..k * 8 + 8].try_into().unwrap()));
+        blake2b_compress(&mut h, &m, &[t as u64, (t >> 64) as u64], &[0, 0], 12);
+        b.copy_within(POW_BB.., 0);
+        b[POW_BB..].fill(0);
+        o -= POW_BB;
+        l -= POW_BB;
+    }
+    let m = core::array::from_fn(|k| u64::from_le_bytes(b[k * 8..k * 8 + 8].try_into().unwrap()));
+    Pow { h, t, m, l, o, n }
+}
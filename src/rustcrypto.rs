@@ -0,0 +1,358 @@
+//! Implementations of the RustCrypto `digest` traits, so that BLAKE2b and BLAKE2s can be used with crates that are
+//! generic over `Digest`, `VariableOutput` or `Mac`.
+
+use crate::blake2b::{blake2b, blake2b_params, Blake2b};
+use crate::blake2s::{blake2s, blake2s_params, Blake2s};
+use core::marker::PhantomData;
+use digest::crypto_common::{BlockSizeUser, KeySizeUser};
+use digest::generic_array::ArrayLength;
+use digest::typenum::{IsLessOrEqual, LeEq, NonZero, Unsigned, U128, U16, U32, U64};
+use digest::{
+    FixedOutput, FixedOutputReset, HashMarker, InvalidBufferSize, InvalidLength, InvalidOutputSize, Key, KeyInit,
+    MacMarker, Output, OutputSizeUser, Reset, Update, VariableOutput,
+};
+
+/// BLAKE2b with the digest length chosen at runtime.
+#[derive(Clone)]
+pub struct Blake2bVar {
+    h: Blake2b,
+    n: usize,
+}
+
+impl Update for Blake2bVar {
+    fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+}
+
+impl VariableOutput for Blake2bVar {
+    const MAX_OUTPUT_SIZE: usize = 64;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        if output_size == 0 || output_size > Self::MAX_OUTPUT_SIZE {
+            return Err(InvalidOutputSize);
+        }
+        let mut p = blake2b_params();
+        p.digest(output_size as u8);
+        Ok(Self { h: blake2b(p), n: output_size })
+    }
+
+    fn output_size(&self) -> usize {
+        self.n
+    }
+
+    fn finalize_variable(mut self, out: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        if out.len() != self.n {
+            return Err(InvalidBufferSize);
+        }
+        self.h.digest(out);
+        Ok(())
+    }
+}
+
+/// BLAKE2s with the digest length chosen at runtime.
+#[derive(Clone)]
+pub struct Blake2sVar {
+    h: Blake2s,
+    n: usize,
+}
+
+impl Update for Blake2sVar {
+    fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+}
+
+impl VariableOutput for Blake2sVar {
+    const MAX_OUTPUT_SIZE: usize = 32;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        if output_size == 0 || output_size > Self::MAX_OUTPUT_SIZE {
+            return Err(InvalidOutputSize);
+        }
+        let mut p = blake2s_params();
+        p.digest(output_size as u8);
+        Ok(Self { h: blake2s(p), n: output_size })
+    }
+
+    fn output_size(&self) -> usize {
+        self.n
+    }
+
+    fn finalize_variable(mut self, out: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        if out.len() != self.n {
+            return Err(InvalidBufferSize);
+        }
+        self.h.digest(out);
+        Ok(())
+    }
+}
+
+/// BLAKE2b with a digest length of N bytes fixed at compile time.
+#[derive(Clone)]
+pub struct Blake2bFix<N> {
+    h: Blake2b,
+    n: PhantomData<N>,
+}
+
+impl<N> Default for Blake2bFix<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    fn default() -> Self {
+        let mut p = blake2b_params();
+        p.digest(N::U8);
+        Self { h: blake2b(p), n: PhantomData }
+    }
+}
+
+impl<N> Update for Blake2bFix<N> {
+    fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+}
+
+impl<N: ArrayLength<u8>> OutputSizeUser for Blake2bFix<N> {
+    type OutputSize = N;
+}
+
+impl<N> BlockSizeUser for Blake2bFix<N> {
+    type BlockSize = U128;
+}
+
+impl<N: ArrayLength<u8>> FixedOutput for Blake2bFix<N> {
+    fn finalize_into(mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+    }
+}
+
+impl<N> Reset for Blake2bFix<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl<N> FixedOutputReset for Blake2bFix<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+        self.reset();
+    }
+}
+
+impl<N> HashMarker for Blake2bFix<N> {}
+
+/// BLAKE2s with a digest length of N bytes fixed at compile time.
+#[derive(Clone)]
+pub struct Blake2sFix<N> {
+    h: Blake2s,
+    n: PhantomData<N>,
+}
+
+impl<N> Default for Blake2sFix<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U32>,
+    LeEq<N, U32>: NonZero,
+{
+    fn default() -> Self {
+        let mut p = blake2s_params();
+        p.digest(N::U8);
+        Self { h: blake2s(p), n: PhantomData }
+    }
+}
+
+impl<N> Update for Blake2sFix<N> {
+    fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+}
+
+impl<N: ArrayLength<u8>> OutputSizeUser for Blake2sFix<N> {
+    type OutputSize = N;
+}
+
+impl<N> BlockSizeUser for Blake2sFix<N> {
+    type BlockSize = U64;
+}
+
+impl<N: ArrayLength<u8>> FixedOutput for Blake2sFix<N> {
+    fn finalize_into(mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+    }
+}
+
+impl<N> Reset for Blake2sFix<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U32>,
+    LeEq<N, U32>: NonZero,
+{
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl<N> FixedOutputReset for Blake2sFix<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U32>,
+    LeEq<N, U32>: NonZero,
+{
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+        self.reset();
+    }
+}
+
+impl<N> HashMarker for Blake2sFix<N> {}
+
+/// Keyed BLAKE2b with a tag length of N bytes. The key may be of any length in [0, 64].
+#[derive(Clone)]
+pub struct Blake2bMac<N> {
+    /// Running state.
+    h: Blake2b,
+    /// State right after keying, used to reset.
+    i: Blake2b,
+    n: PhantomData<N>,
+}
+
+impl<N> KeySizeUser for Blake2bMac<N> {
+    type KeySize = U64;
+}
+
+impl<N> KeyInit for Blake2bMac<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    fn new(key: &Key<Self>) -> Self {
+        Self::new_from_slice(key).unwrap()
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        if key.len() > U64::USIZE {
+            return Err(InvalidLength);
+        }
+        let mut p = blake2b_params();
+        p.digest(N::U8);
+        p.key(key);
+        let h = blake2b(p);
+        Ok(Self { h: h.clone(), i: h, n: PhantomData })
+    }
+}
+
+impl<N> Update for Blake2bMac<N> {
+    fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+}
+
+impl<N: ArrayLength<u8>> OutputSizeUser for Blake2bMac<N> {
+    type OutputSize = N;
+}
+
+impl<N: ArrayLength<u8>> FixedOutput for Blake2bMac<N> {
+    fn finalize_into(mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+    }
+}
+
+impl<N> Reset for Blake2bMac<N> {
+    fn reset(&mut self) {
+        self.h = self.i.clone();
+    }
+}
+
+impl<N: ArrayLength<u8>> FixedOutputReset for Blake2bMac<N> {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+        self.reset();
+    }
+}
+
+impl<N> MacMarker for Blake2bMac<N> {}
+
+/// Keyed BLAKE2s with a tag length of N bytes. The key may be of any length in [0, 32].
+#[derive(Clone)]
+pub struct Blake2sMac<N> {
+    /// Running state.
+    h: Blake2s,
+    /// State right after keying, used to reset.
+    i: Blake2s,
+    n: PhantomData<N>,
+}
+
+impl<N> KeySizeUser for Blake2sMac<N> {
+    type KeySize = U32;
+}
+
+impl<N> KeyInit for Blake2sMac<N>
+where
+    N: ArrayLength<u8> + IsLessOrEqual<U32>,
+    LeEq<N, U32>: NonZero,
+{
+    fn new(key: &Key<Self>) -> Self {
+        Self::new_from_slice(key).unwrap()
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        if key.len() > U32::USIZE {
+            return Err(InvalidLength);
+        }
+        let mut p = blake2s_params();
+        p.digest(N::U8);
+        p.key(key);
+        let h = blake2s(p);
+        Ok(Self { h: h.clone(), i: h, n: PhantomData })
+    }
+}
+
+impl<N> Update for Blake2sMac<N> {
+    fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+}
+
+impl<N: ArrayLength<u8>> OutputSizeUser for Blake2sMac<N> {
+    type OutputSize = N;
+}
+
+impl<N: ArrayLength<u8>> FixedOutput for Blake2sMac<N> {
+    fn finalize_into(mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+    }
+}
+
+impl<N> Reset for Blake2sMac<N> {
+    fn reset(&mut self) {
+        self.h = self.i.clone();
+    }
+}
+
+impl<N: ArrayLength<u8>> FixedOutputReset for Blake2sMac<N> {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        self.h.digest(out);
+        self.reset();
+    }
+}
+
+impl<N> MacMarker for Blake2sMac<N> {}
+
+/// BLAKE2b-256.
+pub type Blake2b256 = Blake2bFix<U32>;
+/// BLAKE2b-512.
+pub type Blake2b512 = Blake2bFix<U64>;
+/// BLAKE2s-128.
+pub type Blake2s128 = Blake2sFix<U16>;
+/// BLAKE2s-256.
+pub type Blake2s256 = Blake2sFix<U32>;
+/// Keyed BLAKE2b-512.
+pub type Blake2bMac512 = Blake2bMac<U64>;
+/// Keyed BLAKE2s-256.
+pub type Blake2sMac256 = Blake2sMac<U32>;
@@ -0,0 +1,192 @@
+//! Power-on self-test of the BLAKE2b and BLAKE2s implementations, with the self-test of RFC 7693 Appendix E and a few
+//! keyed, salted and personalized vectors. Only the RFC 7693 grand hashes are published values; the keyed, salted and
+//! personalized vectors were generated for this crate with Python's hashlib, which wraps the BLAKE2 reference code.
+
+use crate::blake2b::{blake2b, blake2b_params};
+use crate::blake2s::{blake2s, blake2s_params};
+
+/// Grand hash of the BLAKE2b self-test in RFC 7693 Appendix E.
+const B2B_RES: [u8; 32] = [
+    0xc2, 0x3a, 0x78, 0x00, 0xd9, 0x81, 0x23, 0xbd, 0x10, 0xf5, 0x06, 0xc6, 0x1e, 0x29, 0xda, 0x56, 0x03, 0xd7, 0x63,
+    0xb8, 0xbb, 0xad, 0x2e, 0x73, 0x7f, 0x5e, 0x76, 0x5a, 0x7b, 0xcc, 0xd4, 0x75,
+];
+/// Grand hash of the BLAKE2s self-test in RFC 7693 Appendix E.
+const B2S_RES: [u8; 32] = [
+    0x6a, 0x41, 0x1f, 0x08, 0xce, 0x25, 0xad, 0xcd, 0xfb, 0x02, 0xab, 0xa6, 0x41, 0x45, 0x1c, 0xec, 0x53, 0xc5, 0x98,
+    0xb2, 0x4f, 0x4f, 0xc7, 0x87, 0xfb, 0xdc, 0x88, 0x79, 0x7f, 0x4c, 0x1d, 0xfe,
+];
+/// BLAKE2b-512 of MESSAGE keyed with bytes 0 to 63.
+const B2B_KEYED: [u8; 64] = [
+    0x1d, 0x58, 0xd7, 0x14, 0x14, 0xd2, 0x47, 0x52, 0xdb, 0x32, 0x74, 0xaf, 0xdc, 0x48, 0x3f, 0xc0, 0xf4, 0xc6, 0x83,
+    0x17, 0xc4, 0xc2, 0xf6, 0xa3, 0x1e, 0x09, 0xde, 0x94, 0x37, 0xba, 0x02, 0xcc, 0xab, 0x8c, 0x85, 0x85, 0x79, 0x0a,
+    0x52, 0xb0, 0xd4, 0x76, 0xf7, 0x92, 0x0c, 0x0e, 0x13, 0x97, 0xd1, 0xae, 0xc9, 0xe5, 0x2f, 0x3d, 0xf3, 0xfe, 0xae,
+    0x76, 0xf7, 0xd6, 0x22, 0x3c, 0xe5, 0xcf,
+];
+/// BLAKE2b-256 of MESSAGE salted with bytes 0 to 15.
+const B2B_SALTED: [u8; 32] = [
+    0x43, 0xb7, 0xfe, 0xaa, 0x91, 0x01, 0x9d, 0x0d, 0x5b, 0x49, 0x23, 0x57, 0xfb, 0x92, 0x32, 0x11, 0xaf, 0x82, 0x7d,
+    0x61, 0x26, 0xaf, 0x28, 0xcc, 0xc1, 0x87, 0x4e, 0x70, 0xbc, 0x21, 0x77, 0xf8,
+];
+/// BLAKE2b-256 of MESSAGE personalized with "blake2ya selftst".
+const B2B_PERSONAL: [u8; 32] = [
+    0xd4, 0x42, 0x2e, 0x29, 0x99, 0x02, 0xe4, 0x47, 0xc2, 0x58, 0xb8, 0x8c, 0x58, 0x55, 0xc9, 0xb0, 0xb1, 0xab, 0x57,
+    0x2b, 0xb5, 0xd1, 0xd7, 0x5b, 0xf5, 0x16, 0x27, 0xfe, 0x9b, 0xb1, 0xfc, 0x5d,
+];
+/// BLAKE2s-256 of MESSAGE keyed with bytes 0 to 31.
+const B2S_KEYED: [u8; 32] = [
+    0x96, 0xb9, 0x46, 0x3f, 0x88, 0x7b, 0xa8, 0xeb, 0x74, 0x44, 0x07, 0x42, 0x45, 0xf3, 0xb2, 0xf8, 0x38, 0xd6, 0xee,
+    0x5a, 0x65, 0x94, 0x90, 0xad, 0xcf, 0x7a, 0xe8, 0x0c, 0xc9, 0x7b, 0xc6, 0x7f,
+];
+/// BLAKE2s-128 of MESSAGE salted with bytes 0 to 7.
+const B2S_SALTED: [u8; 16] =
+    [0x6f, 0xa5, 0x27, 0x64, 0x68, 0x36, 0x23, 0x3f, 0x3c, 0xbc, 0xe3, 0x63, 0x52, 0xb7, 0x97, 0x20];
+/// BLAKE2s-128 of MESSAGE personalized with "selftest".
+const B2S_PERSONAL: [u8; 16] =
+    [0x30, 0x7e, 0xd9, 0x16, 0x9d, 0xc5, 0xaf, 0x6f, 0x32, 0x0d, 0xfb, 0x3d, 0x72, 0xae, 0x53, 0x32];
+
+/// Message of the keyed, salted and personalized vectors.
+const MESSAGE: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+/// A self-test check that produced a wrong digest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelftestError {
+    /// The BLAKE2b self-test of RFC 7693 Appendix E.
+    Blake2bRfc7693,
+    /// The BLAKE2s self-test of RFC 7693 Appendix E.
+    Blake2sRfc7693,
+    Blake2bKeyed,
+    Blake2bSalted,
+    Blake2bPersonal,
+    Blake2sKeyed,
+    Blake2sSalted,
+    Blake2sPersonal,
+}
+
+impl core::fmt::Display for SelftestError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Self::Blake2bRfc7693 => "BLAKE2b RFC 7693 self-test",
+            Self::Blake2sRfc7693 => "BLAKE2s RFC 7693 self-test",
+            Self::Blake2bKeyed => "BLAKE2b keyed vector",
+            Self::Blake2bSalted => "BLAKE2b salted vector",
+            Self::Blake2bPersonal => "BLAKE2b personalized vector",
+            Self::Blake2sKeyed => "BLAKE2s keyed vector",
+            Self::Blake2sSalted => "BLAKE2s salted vector",
+            Self::Blake2sPersonal => "BLAKE2s personalized vector",
+        };
+        write!(f, "{} failed", s)
+    }
+}
+
+impl core::error::Error for SelftestError {}
+
+/// Deterministic sequence of RFC 7693 Appendix E.
+fn selftest_seq(out: &mut [u8], seed: u32) {
+    let mut a = 0xdead4bad_u32.wrapping_mul(seed);
+    let mut b = 1_u32;
+    for e in out {
+        let t = a.wrapping_add(b);
+        a = b;
+        b = t;
+        *e = (t >> 24) as u8;
+    }
+}
+
+fn rfc7693_2b() -> bool {
+    let mut p = blake2b_params();
+    p.digest(32);
+    let mut g = blake2b(p);
+    let mut data = [0; 1024];
+    let mut key = [0; 64];
+    let mut md = [0; 64];
+    for outlen in [20, 32, 48, 64] {
+        for inlen in [0, 3, 128, 129, 255, 1024] {
+            selftest_seq(&mut data[..inlen], inlen as u32);
+            selftest_seq(&mut key[..outlen], outlen as u32);
+            for k in [&[][..], &key[..outlen]] {
+                let mut p = blake2b_params();
+                p.digest(outlen as u8);
+                p.key(k);
+                let mut h = blake2b(p);
+                h.update(&data[..inlen]);
+                h.digest(&mut md[..outlen]);
+                g.update(&md[..outlen]);
+            }
+        }
+    }
+    g.digest(&mut md[..32]);
+    md[..32] == B2B_RES
+}
+
+fn rfc7693_2s() -> bool {
+    let mut p = blake2s_params();
+    p.digest(32);
+    let mut g = blake2s(p);
+    let mut data = [0; 1024];
+    let mut key = [0; 32];
+    let mut md = [0; 32];
+    for outlen in [16, 20, 28, 32] {
+        for inlen in [0, 3, 64, 65, 255, 1024] {
+            selftest_seq(&mut data[..inlen], inlen as u32);
+            selftest_seq(&mut key[..outlen], outlen as u32);
+            for k in [&[][..], &key[..outlen]] {
+                let mut p = blake2s_params();
+                p.digest(outlen as u8);
+                p.key(k);
+                let mut h = blake2s(p);
+                h.update(&data[..inlen]);
+                h.digest(&mut md[..outlen]);
+                g.update(&md[..outlen]);
+            }
+        }
+    }
+    g.digest(&mut md);
+    md == B2S_RES
+}
+
+/// Hash MESSAGE with BLAKE2b and compare with the expected digest, whose length is the digest length.
+fn vector_2b(key: &[u8], salt: &[u8], person: &[u8], e: &[u8]) -> bool {
+    let mut p = blake2b_params();
+    p.digest(e.len() as u8);
+    p.key(key);
+    p.salt(salt);
+    p.person(person);
+    let mut h = blake2b(p);
+    h.update(MESSAGE);
+    let mut d = [0; 64];
+    h.digest(&mut d[..e.len()]);
+    d[..e.len()] == *e
+}
+
+/// Hash MESSAGE with BLAKE2s and compare with the expected digest, whose length is the digest length.
+fn vector_2s(key: &[u8], salt: &[u8], person: &[u8], e: &[u8]) -> bool {
+    let mut p = blake2s_params();
+    p.digest(e.len() as u8);
+    p.key(key);
+    p.salt(salt);
+    p.person(person);
+    let mut h = blake2s(p);
+    h.update(MESSAGE);
+    let mut d = [0; 32];
+    h.digest(&mut d[..e.len()]);
+    d[..e.len()] == *e
+}
+
+/// Run every check and return the first one that fails. It needs no allocation, and about 2 KiB of stack.
+pub fn selftest() -> Result<(), SelftestError> {
+    let k: [u8; 64] = core::array::from_fn(|i| i as u8);
+    let checks = [
+        (rfc7693_2b(), SelftestError::Blake2bRfc7693),
+        (rfc7693_2s(), SelftestError::Blake2sRfc7693),
+        (vector_2b(&k, &[], &[], &B2B_KEYED), SelftestError::Blake2bKeyed),
+        (vector_2b(&[], &k[..16], &[], &B2B_SALTED), SelftestError::Blake2bSalted),
+        (vector_2b(&[], &[], b"blake2ya selftst", &B2B_PERSONAL), SelftestError::Blake2bPersonal),
+        (vector_2s(&k[..32], &[], &[], &B2S_KEYED), SelftestError::Blake2sKeyed),
+        (vector_2s(&[], &k[..8], &[], &B2S_SALTED), SelftestError::Blake2sSalted),
+        (vector_2s(&[], &[], b"selftest", &B2S_PERSONAL), SelftestError::Blake2sPersonal),
+    ];
+    match checks.into_iter().find(|c| !c.0) {
+        Some((_, e)) => Err(e),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,129 @@
+//! The BLAKE2b functions of libsodium: crypto_generichash, its salt and personal variant, its streaming interface, and
+//! crypto_kdf_derive_from_key. Lengths are checked as libsodium does, and a length it rejects with -1 is an error here.
+
+use crate::blake2b::{blake2b, blake2b_params, Blake2b};
+
+pub const CRYPTO_GENERICHASH_BLAKE2B_BYTES_MIN: usize = 16;
+pub const CRYPTO_GENERICHASH_BLAKE2B_BYTES_MAX: usize = 64;
+pub const CRYPTO_GENERICHASH_BLAKE2B_BYTES: usize = 32;
+pub const CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES_MIN: usize = 16;
+pub const CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES_MAX: usize = 64;
+pub const CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES: usize = 32;
+pub const CRYPTO_GENERICHASH_BLAKE2B_SALTBYTES: usize = 16;
+pub const CRYPTO_GENERICHASH_BLAKE2B_PERSONALBYTES: usize = 16;
+pub const CRYPTO_GENERICHASH_BYTES_MIN: usize = CRYPTO_GENERICHASH_BLAKE2B_BYTES_MIN;
+pub const CRYPTO_GENERICHASH_BYTES_MAX: usize = CRYPTO_GENERICHASH_BLAKE2B_BYTES_MAX;
+pub const CRYPTO_GENERICHASH_BYTES: usize = CRYPTO_GENERICHASH_BLAKE2B_BYTES;
+pub const CRYPTO_GENERICHASH_KEYBYTES_MIN: usize = CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES_MIN;
+pub const CRYPTO_GENERICHASH_KEYBYTES_MAX: usize = CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES_MAX;
+pub const CRYPTO_GENERICHASH_KEYBYTES: usize = CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES;
+pub const CRYPTO_KDF_BLAKE2B_BYTES_MIN: usize = 16;
+pub const CRYPTO_KDF_BLAKE2B_BYTES_MAX: usize = 64;
+pub const CRYPTO_KDF_BLAKE2B_CONTEXTBYTES: usize = 8;
+pub const CRYPTO_KDF_BLAKE2B_KEYBYTES: usize = 32;
+pub const CRYPTO_KDF_BYTES_MIN: usize = CRYPTO_KDF_BLAKE2B_BYTES_MIN;
+pub const CRYPTO_KDF_BYTES_MAX: usize = CRYPTO_KDF_BLAKE2B_BYTES_MAX;
+pub const CRYPTO_KDF_CONTEXTBYTES: usize = CRYPTO_KDF_BLAKE2B_CONTEXTBYTES;
+pub const CRYPTO_KDF_KEYBYTES: usize = CRYPTO_KDF_BLAKE2B_KEYBYTES;
+
+/// An output or key length that libsodium rejects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SodiumError;
+
+impl core::fmt::Display for SodiumError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid output or key length")
+    }
+}
+
+impl core::error::Error for SodiumError {}
+
+/// Streaming state of crypto_generichash.
+#[derive(Clone)]
+pub struct Generichash {
+    h: Blake2b,
+}
+
+impl Generichash {
+    /// crypto_generichash_update().
+    pub fn update(&mut self, data: &[u8]) {
+        self.h.update(data);
+    }
+
+    /// crypto_generichash_final(). Like libsodium, an output shorter than the length given at init receives a prefix
+    /// of the digest.
+    pub fn finalize(mut self, out: &mut [u8]) -> Result<(), SodiumError> {
+        if !(1..=CRYPTO_GENERICHASH_BLAKE2B_BYTES_MAX).contains(&out.len()) {
+            return Err(SodiumError);
+        }
+        let r = self.h.finalize();
+        out.copy_from_slice(&r[..out.len()]);
+        Ok(())
+    }
+}
+
+/// crypto_generichash_blake2b_init_salt_personal(). A missing salt or personalization is zero, and an empty key is no
+/// key.
+pub fn crypto_generichash_blake2b_init_salt_personal(
+    key: &[u8],
+    outlen: usize,
+    salt: Option<&[u8; CRYPTO_GENERICHASH_BLAKE2B_SALTBYTES]>,
+    personal: Option<&[u8; CRYPTO_GENERICHASH_BLAKE2B_PERSONALBYTES]>,
+) -> Result<Generichash, SodiumError> {
+    if !(1..=CRYPTO_GENERICHASH_BLAKE2B_BYTES_MAX).contains(&outlen)
+        || key.len() > CRYPTO_GENERICHASH_BLAKE2B_KEYBYTES_MAX
+    {
+        return Err(SodiumError);
+    }
+    let mut p = blake2b_params();
+    p.digest(outlen as u8);
+    p.key(key);
+    if let Some(s) = salt {
+        p.salt(s);
+    }
+    if let Some(s) = personal {
+        p.person(s);
+    }
+    Ok(Generichash { h: blake2b(p) })
+}
+
+/// crypto_generichash_init(). An empty key is no key.
+pub fn crypto_generichash_init(key: &[u8], outlen: usize) -> Result<Generichash, SodiumError> {
+    crypto_generichash_blake2b_init_salt_personal(key, outlen, None, None)
+}
+
+/// crypto_generichash_blake2b_salt_personal(). The digest fills out, of 1 to 64 bytes.
+pub fn crypto_generichash_blake2b_salt_personal(
+    out: &mut [u8],
+    data: &[u8],
+    key: &[u8],
+    salt: Option<&[u8; CRYPTO_GENERICHASH_BLAKE2B_SALTBYTES]>,
+    personal: Option<&[u8; CRYPTO_GENERICHASH_BLAKE2B_PERSONALBYTES]>,
+) -> Result<(), SodiumError> {
+    let mut h = crypto_generichash_blake2b_init_salt_personal(key, out.len(), salt, personal)?;
+    h.update(data);
+    h.finalize(out)
+}
+
+/// crypto_generichash(). The digest fills out, of 1 to 64 bytes.
+pub fn crypto_generichash(out: &mut [u8], data: &[u8], key: &[u8]) -> Result<(), SodiumError> {
+    crypto_generichash_blake2b_salt_personal(out, data, key, None, None)
+}
+
+/// crypto_kdf_derive_from_key(): BLAKE2b of nothing, keyed by the master key, with the little-endian subkey id as the
+/// salt and the context as the personalization. The subkey fills out, of 16 to 64 bytes.
+pub fn crypto_kdf_derive_from_key(
+    subkey: &mut [u8],
+    subkey_id: u64,
+    ctx: &[u8; CRYPTO_KDF_BLAKE2B_CONTEXTBYTES],
+    key: &[u8; CRYPTO_KDF_BLAKE2B_KEYBYTES],
+) -> Result<(), SodiumError> {
+    if !(CRYPTO_KDF_BLAKE2B_BYTES_MIN..=CRYPTO_KDF_BLAKE2B_BYTES_MAX).contains(&subkey.len()) {
+        return Err(SodiumError);
+    }
+    let mut salt = [0; CRYPTO_GENERICHASH_BLAKE2B_SALTBYTES];
+    salt[..8].copy_from_slice(&subkey_id.to_le_bytes());
+    let mut personal = [0; CRYPTO_GENERICHASH_BLAKE2B_PERSONALBYTES];
+    personal[..CRYPTO_KDF_BLAKE2B_CONTEXTBYTES].copy_from_slice(ctx);
+    crypto_generichash_blake2b_salt_personal(subkey, &[], key, Some(&salt), Some(&personal))
+}
@@ -0,0 +1,210 @@
+//! The BLAKE2 storage hashers of Substrate and SS58 addresses of 32-byte and 33-byte public keys, without allocation.
+
+use crate::blake2b::{blake2b, blake2b_params};
+
+/// Longest payload: a one-byte or two-byte prefix, a 33-byte key and a two-byte checksum.
+const SS58_DATA: usize = 37;
+/// Base58 characters of the longest payload.
+const SS58_TEXT: usize = 51;
+const SS58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+fn blake2(data: &[&[u8]], r: &mut [u8]) {
+    let mut p = blake2b_params();
+    p.digest(r.len() as u8);
+    let mut h = blake2b(p);
+    data.iter().for_each(|d| h.update(d));
+    h.digest(r);
+}
+
+/// The Blake2_128 storage hasher: BLAKE2b-128.
+pub fn blake2_128(data: &[u8]) -> [u8; 16] {
+    let mut r = [0; 16];
+    blake2(&[data], &mut r);
+    r
+}
+
+/// The Blake2_256 storage hasher: BLAKE2b-256.
+pub fn blake2_256(data: &[u8]) -> [u8; 32] {
+    let mut r = [0; 32];
+    blake2(&[data], &mut r);
+    r
+}
+
+/// BLAKE2b-512.
+pub fn blake2_512(data: &[u8]) -> [u8; 64] {
+    let mut r = [0; 64];
+    blake2(&[data], &mut r);
+    r
+}
+
+/// The Blake2_128Concat storage hasher: BLAKE2b-128 of data followed by data. out is 16 bytes longer than data.
+pub fn blake2_128_concat(data: &[u8], out: &mut [u8]) {
+    assert_eq!(out.len(), 16 + data.len());
+    out[..16].copy_from_slice(&blake2_128(data));
+    out[16..].copy_from_slice(data);
+}
+
+/// The SS58 hash: BLAKE2b-512 of "SS58PRE" followed by the prefix and key. Addresses keep its first two bytes.
+pub fn ss58_hash(data: &[u8]) -> [u8; 64] {
+    let mut r = [0; 64];
+    blake2(&[b"SS58PRE", data], &mut r);
+    r
+}
+
+/// Errors of SS58 addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ss58Error {
+    /// The address type is above 16383, or the prefix byte is not a valid one.
+    Prefix,
+    /// The key is neither 32 nor 33 bytes, or not the expected length.
+    Length,
+    /// A character is not in the base58 alphabet.
+    Base58,
+    /// The checksum does not match.
+    Checksum,
+}
+
+impl core::fmt::Display for Ss58Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let s = match self {
+            Self::Prefix => "invalid SS58 prefix",
+            Self::Length => "invalid SS58 key length",
+            Self::Base58 => "invalid base58 character",
+            Self::Checksum => "invalid SS58 checksum",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for Ss58Error {}
+
+/// An SS58 address.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Ss58 {
+    buf: [u8; SS58_TEXT],
+    n: usize,
+}
+
+impl Ss58 {
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.n]).unwrap()
+    }
+}
+
+impl core::fmt::Display for Ss58 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::fmt::Debug for Ss58 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Encode a public key of 32 or 33 bytes as the SS58 address of a network, such as 0 for Polkadot, 2 for Kusama or 42
+/// for generic Substrate.
+pub fn ss58_encode(prefix: u16, key: &[u8]) -> Result<Ss58, Ss58Error> {
+    if key.len() != 32 && key.len() != 33 {
+        return Err(Ss58Error::Length);
+    }
+    let mut d = [0; SS58_DATA];
+    let p = match prefix {
+        0..=63 => {
+            d[0] = prefix as u8;
+            1
+        }
+        64..=16383 => {
+            d[0] = ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000;
+            d[1] = (prefix >> 8) as u8 | ((prefix & 0b11) << 6) as u8;
+            2
+        }
+        _ => return Err(Ss58Error::Prefix),
+    };
+    d[p..p + key.len()].copy_from_slice(key);
+    let n = p + key.len();
+    let c = ss58_hash(&d[..n]);
+    d[n..n + 2].copy_from_slice(&c[..2]);
+    let d = &d[..n + 2];
+
+    // Base58: repeated division of the big-endian number by 58, one leading '1' per leading zero byte.
+    let mut digits = [0; SS58_TEXT];
+    let mut k = 0;
+    for &b in d {
+        let mut carry = b as u32;
+        for x in &mut digits[..k] {
+            carry += (*x as u32) << 8;
+            *x = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry != 0 {
+            digits[k] = (carry % 58) as u8;
+            k += 1;
+            carry /= 58;
+        }
+    }
+    let z = d.iter().take_while(|&&b| b == 0).count();
+    let mut r = Ss58 { buf: [b'1'; SS58_TEXT], n: z + k };
+    for (o, &x) in r.buf[z..z + k].iter_mut().zip(digits[..k].iter().rev()) {
+        *o = SS58_ALPHABET[x as usize];
+    }
+    Ok(r)
+}
+
+/// Decode an SS58 address into key, which must have the length of the key in the address. Returns the address type.
+pub fn ss58_decode(s: &str, key: &mut [u8]) -> Result<u16, Ss58Error> {
+    if key.len() != 32 && key.len() != 33 {
+        return Err(Ss58Error::Length);
+    }
+    if s.len() > SS58_TEXT {
+        return Err(Ss58Error::Length);
+    }
+    let mut bytes = [0; SS58_DATA + 1];
+    let mut k = 0;
+    for &c in s.as_bytes() {
+        let mut carry = SS58_ALPHABET.iter().position(|&a| a == c).ok_or(Ss58Error::Base58)? as u32;
+        for x in &mut bytes[..k] {
+            carry += *x as u32 * 58;
+            *x = carry as u8;
+            carry >>= 8;
+        }
+        while carry != 0 {
+            if k == bytes.len() {
+                return Err(Ss58Error::Length);
+            }
+            bytes[k] = carry as u8;
+            k += 1;
+            carry >>= 8;
+        }
+    }
+    let z = s.bytes().take_while(|&c| c == b'1').count();
+    let n = z + k;
+    if n > SS58_DATA {
+        return Err(Ss58Error::Length);
+    }
+    let mut d = [0; SS58_DATA];
+    for (o, &x) in d[z..n].iter_mut().zip(bytes[..k].iter().rev()) {
+        *o = x;
+    }
+    let d = &d[..n];
+
+    let (p, prefix) = match d.first() {
+        Some(&b @ 0..=63) => (1, b as u16),
+        Some(&b @ 64..=127) if d.len() > 1 => {
+            let lower = (b << 2) | (d[1] >> 6);
+            let upper = d[1] & 0b0011_1111;
+            (2, lower as u16 | (upper as u16) << 8)
+        }
+        _ => return Err(Ss58Error::Prefix),
+    };
+    if d.len() != p + key.len() + 2 {
+        return Err(Ss58Error::Length);
+    }
+    let c = ss58_hash(&d[..d.len() - 2]);
+    if c[..2] != d[d.len() - 2..] {
+        return Err(Ss58Error::Checksum);
+    }
+    key.copy_from_slice(&d[p..p + key.len()]);
+    Ok(prefix)
+}
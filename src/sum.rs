@@ -0,0 +1,167 @@
+//! Checksums of whole files with any of the BLAKE2 variants, as computed by b2sum.
+
+use crate::blake2b::{blake2b, blake2b_params};
+use crate::blake2bp::blake2bp;
+use crate::blake2s::{blake2s, blake2s_params};
+use crate::blake2sp::blake2sp;
+use crate::blake2xb::{blake2xb, BLAKE2XB_UNKNOWN};
+use crate::blake2xs::{blake2xs, BLAKE2XS_UNKNOWN};
+use std::io::Read;
+use std::vec;
+use std::vec::Vec;
+
+/// BLAKE2 variants supported by b2sum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Blake2b,
+    Blake2s,
+    Blake2bp,
+    Blake2sp,
+}
+
+impl Algorithm {
+    /// Look up an algorithm by its command line name, such as "blake2b". The match is case insensitive.
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "blake2b" => Some(Self::Blake2b),
+            "blake2s" => Some(Self::Blake2s),
+            "blake2bp" => Some(Self::Blake2bp),
+            "blake2sp" => Some(Self::Blake2sp),
+            _ => None,
+        }
+    }
+
+    /// Name used in BSD-style lines, such as "BLAKE2b".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Blake2b => "BLAKE2b",
+            Self::Blake2s => "BLAKE2s",
+            Self::Blake2bp => "BLAKE2bp",
+            Self::Blake2sp => "BLAKE2sp",
+        }
+    }
+
+    /// Maximal digest byte length, which is also the default one.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Blake2b | Self::Blake2bp => 64,
+            Self::Blake2s | Self::Blake2sp => 32,
+        }
+    }
+}
+
+/// Pass all the data from a reader to f, one buffer at a time.
+fn feed(mut r: impl Read, mut f: impl FnMut(&[u8])) -> std::io::Result<()> {
+    let mut b = vec![0; 1 << 16];
+    loop {
+        match r.read(&mut b) {
+            Ok(0) => return Ok(()),
+            Ok(m) => f(&b[..m]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Optional parameters of a checksum. All empty by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SumParams {
+    /// Key, of at most a.size() bytes.
+    pub key: Vec<u8>,
+    /// Salt, of at most 16 bytes for BLAKE2b and BLAKE2bp, and 8 bytes for BLAKE2s and BLAKE2sp.
+    pub salt: Vec<u8>,
+    /// Personalization, of the same length limits as the salt.
+    pub person: Vec<u8>,
+    /// Compute the BLAKE2X output instead, with BLAKE2Xb for BLAKE2b or BLAKE2Xs for BLAKE2s. Its byte length is the
+    /// digest length n passed to sum_params().
+    pub xof: bool,
+}
+
+impl SumParams {
+    /// Whether the key, salt and personalization are within the limits of algorithm a.
+    pub fn fits(&self, a: Algorithm) -> bool {
+        self.key.len() <= a.size() && self.salt.len() <= a.size() / 4 && self.person.len() <= a.size() / 4
+    }
+}
+
+/// Compute the digest of all the data from a reader. The digest byte length is an integer in [1, a.size()].
+pub fn sum(a: Algorithm, n: usize, r: impl Read) -> std::io::Result<Vec<u8>> {
+    sum_params(a, n, &SumParams::default(), r)
+}
+
+/// Compute the digest of all the data from a reader with a key, salt or personalization. The digest byte length is an
+/// integer in [1, a.size()], or the output length of BLAKE2X in [1, 2^32 - 2] for BLAKE2Xb and [1, 2^16 - 2] for
+/// BLAKE2Xs.
+pub fn sum_params(a: Algorithm, n: usize, s: &SumParams, r: impl Read) -> std::io::Result<Vec<u8>> {
+    assert!(s.fits(a));
+    let mut d = vec![0; n];
+    match a {
+        Algorithm::Blake2b | Algorithm::Blake2bp => {
+            let mut p = blake2b_params();
+            p.key(&s.key);
+            p.salt(&s.salt);
+            p.person(&s.person);
+            if s.xof {
+                assert!(a == Algorithm::Blake2b && n >= 1 && n < BLAKE2XB_UNKNOWN as usize);
+                let mut h = blake2xb(p, n as u32);
+                feed(r, |b| h.update(b))?;
+                h.digest(&mut d);
+                return Ok(d);
+            }
+            assert!((1..=a.size()).contains(&n));
+            p.digest(n as u8);
+            if a == Algorithm::Blake2b {
+                let mut h = blake2b(p);
+                feed(r, |b| h.update(b))?;
+                h.digest(&mut d);
+            } else {
+                let mut h = blake2bp(p);
+                feed(r, |b| h.update(b))?;
+                h.digest(&mut d);
+            }
+        }
+        Algorithm::Blake2s | Algorithm::Blake2sp => {
+            let mut p = blake2s_params();
+            p.key(&s.key);
+            p.salt(&s.salt);
+            p.person(&s.person);
+            if s.xof {
+                assert!(a == Algorithm::Blake2s && n >= 1 && n < BLAKE2XS_UNKNOWN as usize);
+                let mut h = blake2xs(p, n as u16);
+                feed(r, |b| h.update(b))?;
+                h.digest(&mut d);
+                return Ok(d);
+            }
+            assert!((1..=a.size()).contains(&n));
+            p.digest(n as u8);
+            if a == Algorithm::Blake2s {
+                let mut h = blake2s(p);
+                feed(r, |b| h.update(b))?;
+                h.digest(&mut d);
+            } else {
+                let mut h = blake2sp(p);
+                feed(r, |b| h.update(b))?;
+                h.digest(&mut d);
+            }
+        }
+    }
+    Ok(d)
+}
+
+/// Escape a file name for a checksum line the way b2sum does: backslash, newline and carriage return are written as
+/// escape sequences, and the line must then start with a backslash. None if the name needs no escaping.
+pub fn escape(name: &[u8]) -> Option<Vec<u8>> {
+    if !name.iter().any(|&c| c == b'\\' || c == b'\n' || c == b'\r') {
+        return None;
+    }
+    let mut r = Vec::with_capacity(name.len() + 4);
+    for &c in name {
+        match c {
+            b'\\' => r.extend_from_slice(b"\\\\"),
+            b'\n' => r.extend_from_slice(b"\\n"),
+            b'\r' => r.extend_from_slice(b"\\r"),
+            c => r.push(c),
+        }
+    }
+    Some(r)
+}
@@ -0,0 +1,126 @@
+//! A single BLAKE2b digest over a directory tree, to tell whether two trees hold the same files.
+//!
+//! The tree is walked depth first, with the entries of each directory in byte order of their names. Every entry adds
+//! one record to the root hash:
+//!
+//! | Type      | Record                                                                  |
+//! | --------- | ----------------------------------------------------------------------- |
+//! | Directory | `d`, path                                                               |
+//! | File      | `f`, path, size as u64 le, 1 if executable else 0, 64-byte file digest  |
+//! | Symlink   | `l`, path, target                                                       |
+//!
+//! Paths are relative to the root, with components joined by `/`, and the root itself has the empty path. Paths and
+//! targets are prefixed with their byte length as u64 le. File digests are BLAKE2b-512 of the contents personalized
+//! with "blake2ya.file", and the root hash is personalized with "blake2ya.tree". Timestamps, owners and permissions
+//! other than the executable bit are not part of the digest, and symlinks are not followed.
+
+use crate::blake2b::{blake2b, blake2b_params, Blake2b};
+use std::fs::{self, Metadata};
+use std::io::Read;
+use std::path::Path;
+use std::vec;
+use std::vec::Vec;
+
+const PERSON_FILE: &[u8] = b"blake2ya.file";
+const PERSON_TREE: &[u8] = b"blake2ya.tree";
+
+fn bytes(h: &mut Blake2b, b: &[u8]) {
+    h.update(&(b.len() as u64).to_le_bytes());
+    h.update(b);
+}
+
+#[cfg(unix)]
+fn executable(m: &Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    m.permissions().mode() & 0o100 != 0
+}
+
+#[cfg(not(unix))]
+fn executable(_: &Metadata) -> bool {
+    false
+}
+
+/// Digest the contents of a file, and count its bytes.
+fn file(p: &Path) -> std::io::Result<(u64, [u8; 64])> {
+    let mut r = fs::File::open(p)?;
+    let mut s = blake2b_params();
+    s.digest(64);
+    s.person(PERSON_FILE);
+    let mut h = blake2b(s);
+    let mut b = vec![0; 1 << 16];
+    let mut n = 0;
+    loop {
+        match r.read(&mut b) {
+            Ok(0) => break,
+            Ok(m) => {
+                h.update(&b[..m]);
+                n += m as u64;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    let mut d = [0; 64];
+    h.digest(&mut d);
+    Ok((n, d))
+}
+
+/// Prefix an error with the path it happened at.
+fn at(p: &Path, e: std::io::Error) -> std::io::Error {
+    std::io::Error::new(e.kind(), std::format!("{}: {}", p.display(), e))
+}
+
+/// Add the records of the entry at p, whose path relative to the root is rel, and of everything below it. Errors name
+/// the path they happened at.
+fn walk(h: &mut Blake2b, p: &Path, rel: &mut Vec<u8>) -> std::io::Result<()> {
+    let m = fs::symlink_metadata(p).map_err(|e| at(p, e))?;
+    let t = m.file_type();
+    if t.is_dir() {
+        h.update(b"d");
+        bytes(h, rel);
+        let mut names = fs::read_dir(p)
+            .and_then(|d| d.map(|e| e.map(|e| e.file_name())).collect::<std::io::Result<Vec<_>>>())
+            .map_err(|e| at(p, e))?;
+        names.sort_by(|a, b| a.as_encoded_bytes().cmp(b.as_encoded_bytes()));
+        for name in names {
+            let n = rel.len();
+            if n != 0 {
+                rel.push(b'/');
+            }
+            rel.extend_from_slice(name.as_encoded_bytes());
+            walk(h, &p.join(&name), rel)?;
+            rel.truncate(n);
+        }
+    } else if t.is_file() {
+        let (n, d) = file(p).map_err(|e| at(p, e))?;
+        h.update(b"f");
+        bytes(h, rel);
+        h.update(&n.to_le_bytes());
+        h.update(&[executable(&m) as u8]);
+        h.update(&d);
+    } else if t.is_symlink() {
+        h.update(b"l");
+        bytes(h, rel);
+        bytes(h, fs::read_link(p).map_err(|e| at(p, e))?.as_os_str().as_encoded_bytes());
+    } else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            std::format!("{}: unsupported file type", p.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Compute the digest of the directory tree at p. The digest byte length is an integer in [1, 64]. Devices, sockets
+/// and fifos are an error. Errors are prefixed with the path of the entry they happened at.
+pub fn tree(p: &Path, n: usize) -> std::io::Result<Vec<u8>> {
+    assert!((1..=64).contains(&n));
+    let mut s = blake2b_params();
+    s.digest(n as u8);
+    s.person(PERSON_TREE);
+    let mut h = blake2b(s);
+    walk(&mut h, p, &mut vec![])?;
+    let mut d = vec![0; n];
+    h.digest(&mut d);
+    Ok(d)
+}
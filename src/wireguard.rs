@@ -0,0 +1,102 @@
+//! The BLAKE2s parts of the WireGuard handshake: mac1 and mac2 of handshake messages, and the cookie of the cookie
+//! reply mechanism (WireGuard whitepaper, sections 5.4.4 and 5.4.7). Encrypting the cookie needs XChaCha20-Poly1305,
+//! which is left to the caller.
+
+use crate::blake2s::{blake2s, blake2s_params};
+use crate::noise::{noise_hash_blake2s, SymmetricState2s};
+
+/// Noise construction of WireGuard.
+pub const WG_CONSTRUCTION: &[u8] = b"Noise_IKpsk2_25519_ChaChaPoly_BLAKE2s";
+/// Identifier mixed into the handshake hash after the construction.
+pub const WG_IDENTIFIER: &[u8] = b"WireGuard v1 zx2c4 Jason@zx2c4.com";
+/// Label of the mac1 key.
+pub const WG_LABEL_MAC1: &[u8] = b"mac1----";
+/// Label of the cookie encryption key.
+pub const WG_LABEL_COOKIE: &[u8] = b"cookie--";
+
+/// The symmetric state every handshake starts from: the construction, followed by MixHash(identifier). The
+/// responder's static public key is the next MixHash.
+pub fn wg_symmetric_state() -> SymmetricState2s {
+    let mut s = SymmetricState2s::new(WG_CONSTRUCTION);
+    s.mix_hash(WG_IDENTIFIER);
+    s
+}
+
+/// MAC(key, input): keyed BLAKE2s with a 16-byte digest.
+pub fn wg_mac(key: &[u8], data: &[u8]) -> [u8; 16] {
+    let mut p = blake2s_params();
+    p.digest(16);
+    p.key(key);
+    let mut h = blake2s(p);
+    h.update(data);
+    let mut r = [0; 16];
+    h.digest(&mut r);
+    r
+}
+
+/// Key of mac1 for messages to the peer of this static public key: HASH(LABEL_MAC1 || pubkey).
+pub fn wg_mac1_key(pubkey: &[u8; 32]) -> [u8; 32] {
+    noise_hash_blake2s(&[WG_LABEL_MAC1, pubkey])
+}
+
+/// Key that encrypts cookies sent by the peer of this static public key: HASH(LABEL_COOKIE || pubkey).
+pub fn wg_cookie_key(pubkey: &[u8; 32]) -> [u8; 32] {
+    noise_hash_blake2s(&[WG_LABEL_COOKIE, pubkey])
+}
+
+/// The cookie a responder hands out: MAC(secret, source), where source is the IP address and UDP port of the
+/// initiator as bytes, and the secret changes every two minutes.
+pub fn wg_cookie(secret: &[u8; 32], source: &[u8]) -> [u8; 16] {
+    wg_mac(secret, source)
+}
+
+/// Compute mac1 of a whole handshake message, over all of it up to the mac1 field. mac1 and mac2 are the last 32
+/// bytes of the message, which must be at least 32 bytes.
+pub fn wg_mac1(mac1_key: &[u8; 32], msg: &[u8]) -> [u8; 16] {
+    assert!(msg.len() >= 32);
+    wg_mac(mac1_key, &msg[..msg.len() - 32])
+}
+
+/// Compute mac2 of a whole handshake message, over all of it up to the mac2 field, which is the last 16 bytes. The
+/// message must be at least 16 bytes.
+pub fn wg_mac2(cookie: &[u8; 16], msg: &[u8]) -> [u8; 16] {
+    assert!(msg.len() >= 16);
+    wg_mac(cookie, &msg[..msg.len() - 16])
+}
+
+/// Fill the mac1 and mac2 fields of a handshake message of at least 32 bytes. mac2 is zero without a cookie.
+pub fn wg_set_macs(msg: &mut [u8], mac1_key: &[u8; 32], cookie: Option<&[u8; 16]>) {
+    let n = msg.len();
+    assert!(n >= 32);
+    let mac1 = wg_mac1(mac1_key, msg);
+    msg[n - 32..n - 16].copy_from_slice(&mac1);
+    let mac2 = match cookie {
+        Some(c) => wg_mac2(c, msg),
+        None => [0; 16],
+    };
+    msg[n - 16..].copy_from_slice(&mac2);
+}
+
+/// Compare in constant time.
+fn eq(a: &[u8], b: &[u8]) -> bool {
+    a.iter().zip(b).fold(0, |r, (a, b)| r | (a ^ b)) == 0
+}
+
+/// Check the mac1 field of a handshake message, in constant time. Messages shorter than 32 bytes fail.
+pub fn wg_verify_mac1(mac1_key: &[u8; 32], msg: &[u8]) -> bool {
+    let n = msg.len();
+    if n < 32 {
+        return false;
+    }
+    eq(&wg_mac1(mac1_key, msg), &msg[n - 32..n - 16])
+}
+
+/// Check the mac2 field of a handshake message against the cookie handed out to its source, in constant time.
+/// Messages shorter than 16 bytes fail.
+pub fn wg_verify_mac2(cookie: &[u8; 16], msg: &[u8]) -> bool {
+    let n = msg.len();
+    if n < 16 {
+        return false;
+    }
+    eq(&wg_mac2(cookie, msg), &msg[n - 16..])
+}
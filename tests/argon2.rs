@@ -0,0 +1,88 @@
+//! Test vectors of RFC 9106 section 5.
+
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn rfc9106(y: blake2ya::Argon2Type) -> String {
+    let mut a = blake2ya::argon2(y);
+    a.memory(32);
+    a.time(3);
+    a.lanes(4);
+    a.secret(&[3; 8]);
+    a.data(&[4; 12]);
+    let mut memory = vec![blake2ya::Argon2Block::default(); a.memory_blocks()];
+    let mut out = [0; 32];
+    a.hash_with_memory(&[1; 32], &[2; 16], &mut out, &mut memory).unwrap();
+    hex(&out)
+}
+
+#[test]
+fn argon2_rfc9106() {
+    use blake2ya::Argon2Type::*;
+    assert_eq!(rfc9106(Argon2d), "512b391b6f1162975371d30919734294f868e3be3984f3c1a13a4db9fabe4acb");
+    assert_eq!(rfc9106(Argon2i), "c814d9d1dc7f37aa13f0d77f2494bda1c8de6b016dd388d29952a4c4672b6ce8");
+    assert_eq!(rfc9106(Argon2id), "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659");
+}
+
+#[test]
+fn argon2_errors() {
+    use blake2ya::Argon2Error;
+    let mut a = blake2ya::argon2(blake2ya::Argon2Type::Argon2id);
+    a.memory(32);
+    let mut memory = vec![blake2ya::Argon2Block::default(); 31];
+    let mut out = [0; 32];
+    assert_eq!(a.hash_with_memory(b"", &[0; 8], &mut out, &mut memory), Err(Argon2Error::Blocks));
+    assert_eq!(a.hash_with_memory(b"", &[0; 7], &mut out, &mut memory), Err(Argon2Error::Salt));
+    assert_eq!(a.hash_with_memory(b"", &[0; 8], &mut out[..3], &mut memory), Err(Argon2Error::Output));
+    a.lanes(5);
+    assert_eq!(a.hash_with_memory(b"", &[0; 8], &mut out, &mut memory), Err(Argon2Error::Memory));
+    a.lanes(0);
+    assert_eq!(a.hash_with_memory(b"", &[0; 8], &mut out, &mut memory), Err(Argon2Error::Lanes));
+    a.lanes(1);
+    a.time(0);
+    assert_eq!(a.hash_with_memory(b"", &[0; 8], &mut out, &mut memory), Err(Argon2Error::Time));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn argon2_threads() {
+    let mut a = blake2ya::argon2(blake2ya::Argon2Type::Argon2id);
+    a.memory(256);
+    a.lanes(5);
+    let mut one = [0; 40];
+    a.hash(b"password", b"somesalt", &mut one).unwrap();
+    for n in [2, 3, 8] {
+        a.threads(n);
+        let mut out = [0; 40];
+        a.hash(b"password", b"somesalt", &mut out).unwrap();
+        assert_eq!(out, one);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn argon2_phc() {
+    // Vector of the argon2 command line tool of the reference implementation.
+    let s = "$argon2i$v=19$m=65536,t=2,p=4$c29tZXNhbHQ$IMit9qkFULCMA/ViizL57cnTLOa5DiVM9eMwpAvPwr4";
+    let a = blake2ya::argon2(blake2ya::Argon2Type::Argon2id);
+    assert_eq!(a.verify(s, b"password"), Ok(true));
+    assert_eq!(a.verify(s, b"passwore"), Ok(false));
+    let mut a = blake2ya::argon2(blake2ya::Argon2Type::Argon2d);
+    a.memory(64);
+    a.time(1);
+    a.lanes(2);
+    let e = a.hash_encoded(b"password", b"saltsaltsalt", 20).unwrap();
+    assert!(e.starts_with("$argon2d$v=19$m=64,t=1,p=2$c2FsdHNhbHRzYWx0$"));
+    assert_eq!(a.verify(&e, b"password"), Ok(true));
+    for bad in [
+        "$argon2i$v=16$m=65536,t=2,p=4$c29tZXNhbHQ$IMit9qkFULCMA/ViizL57cnTLOa5DiVM9eMwpAvPwr4",
+        "$argon2x$v=19$m=65536,t=2,p=4$c29tZXNhbHQ$IMit9qkFULCMA/ViizL57cnTLOa5DiVM9eMwpAvPwr4",
+        "$argon2i$v=19$m=065536,t=2,p=4$c29tZXNhbHQ$IMit9qkFULCMA/ViizL57cnTLOa5DiVM9eMwpAvPwr4",
+        "$argon2i$v=19$t=2,m=65536,p=4$c29tZXNhbHQ$IMit9qkFULCMA/ViizL57cnTLOa5DiVM9eMwpAvPwr4",
+        "$argon2i$v=19$m=65536,t=2,p=4$c29tZXNhbHQ$IMit9qkFULCMA/ViizL57cnTLOa5DiVM9eMwpAvPwr5",
+        "$argon2i$v=19$m=65536,t=2,p=4$c29tZXNhbHQ=$IMit9qkFULCMA/ViizL57cnTLOa5DiVM9eMwpAvPwr4",
+    ] {
+        assert_eq!(a.verify(bad, b"password"), Err(blake2ya::Argon2Error::Encoding));
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "std")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn b2sum(args: &[&str], stdin: &[u8]) -> (i32, String, String) {
+    let mut c = Command::new(env!("CARGO_BIN_EXE_b2sum-ya"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The program may exit on a usage error before reading its input.
+    let _ = c.stdin.take().unwrap().write_all(stdin);
+    let o = c.wait_with_output().unwrap();
+    (o.status.code().unwrap(), String::from_utf8(o.stdout).unwrap(), String::from_utf8(o.stderr).unwrap())
+}
+
+#[test]
+fn b2sum_stdin() {
+    let (code, out, _) = b2sum(&[], b"abc");
+    assert_eq!(code, 0);
+    assert_eq!(
+        out,
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923  -\n"
+    );
+    let (_, out, _) = b2sum(&["-a", "blake2s", "-l", "128", "-"], b"abc");
+    assert_eq!(out, "aa4938119b1dc7b87cbad0ffd200d0ae  -\n");
+}
+
+#[test]
+fn b2sum_tag() {
+    let (_, out, _) = b2sum(&["--tag", "-a", "blake2s"], b"abc");
+    assert_eq!(out, "BLAKE2s (-) = 508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982\n");
+    let (_, out, _) = b2sum(&["--tag", "-zl256"], b"abc");
+    assert_eq!(out, "BLAKE2b-256 (-) = bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319\0");
+}
+
+#[test]
+fn b2sum_error() {
+    let (code, out, err) = b2sum(&["-l", "12"], b"");
+    assert_eq!((code, out.as_str()), (1, ""));
+    assert!(err.contains("length is not a multiple of 8"));
+    let (code, out, err) = b2sum(&["-a", "blake2s", "-l", "512"], b"");
+    assert_eq!((code, out.as_str()), (1, ""));
+    assert!(err.contains("invalid length"));
+    let (code, out, err) = b2sum(&["/nonexistent", "-"], b"");
+    assert_eq!(code, 1);
+    assert!(out.ends_with("  -\n"));
+    assert!(err.contains("/nonexistent: No such file or directory"));
+}
+
+#[test]
+fn b2sum_check() {
+    let (_, sums, _) = b2sum(&["--tag", "-l", "256", "-", "-"], b"abc");
+    let manifest = std::env::temp_dir().join(format!("blake2ya-b2sum-{}", std::process::id()));
+    std::fs::write(&manifest, sums).unwrap();
+    let m = manifest.to_str().unwrap();
+    let (code, out, _) = b2sum(&["-c", m], b"abc");
+    assert_eq!((code, out.as_str()), (0, "-: OK\n-: OK\n"));
+    let (code, out, _) = b2sum(&["-c", m], b"abd");
+    assert_eq!((code, out.as_str()), (1, "-: FAILED\n-: OK\n"));
+    let (code, out, err) = b2sum(&["-c", "--status", m], b"abd");
+    assert_eq!((code, out.as_str(), err.as_str()), (1, "", ""));
+    let (code, _, err) = b2sum(&["-c", "--tag", m], b"");
+    assert_eq!(code, 1);
+    assert!(err.contains("meaningless"));
+    std::fs::remove_file(&manifest).unwrap();
+}
+
+#[test]
+fn b2sum_keyed() {
+    let key = std::env::temp_dir().join(format!("blake2ya-b2sum-key-{}", std::process::id()));
+    std::fs::write(&key, b"secret").unwrap();
+    let k = key.to_str().unwrap();
+    let (code, out, _) = b2sum(&["--key-file", k, "--salt", "73616c74", "--person=6d65", "-l", "256"], b"abc");
+    assert_eq!((code, out.as_str()), (0, "1ed146ac9d84deddd25613224ef518527965f52690083350b9559de923f490e7  -\n"));
+    let manifest = std::env::temp_dir().join(format!("blake2ya-b2sum-tags-{}", std::process::id()));
+    std::fs::write(&manifest, &out).unwrap();
+    let m = manifest.to_str().unwrap();
+    let (code, out, _) = b2sum(&["-c", "--key-file", k, "--salt", "73616c74", "--person=6d65", m], b"abc");
+    assert_eq!((code, out.as_str()), (0, "-: OK\n"));
+    let (code, out, _) = b2sum(&["-c", m], b"abc");
+    assert_eq!((code, out.as_str()), (1, "-: FAILED\n"));
+    let (code, _, err) = b2sum(&["--key-env", "BLAKE2YA_TEST_UNSET_KEY"], b"abc");
+    assert_eq!(code, 1);
+    assert!(err.contains("environment variable not set"));
+    std::fs::remove_file(&key).unwrap();
+    std::fs::remove_file(&manifest).unwrap();
+}
+
+#[test]
+fn b2sum_xof() {
+    let (code, out, _) = b2sum(&["-a", "blake2s", "--xof", "50"], b"abc");
+    assert_eq!(
+        (code, out.as_str()),
+        (
+            0,
+            "3fbda980e22b1efa091320b6b802a41ed42f6017b56c4f9c7c5928facc12e95efdd10ec995d157f202caacbf63f1a0de9504  -\n"
+        )
+    );
+    let o = Command::new(env!("CARGO_BIN_EXE_b2sum-ya"))
+        .args(["-a", "blake2s", "--xof=50", "--raw", "-"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let (_, out, _) = b2sum(&["-a", "blake2s", "--xof=50"], b"");
+    assert_eq!(o.stdout.iter().map(|b| format!("{:02x}", b)).collect::<String>() + "  -\n", out);
+    let (code, _, _) = b2sum(&["-a", "blake2bp", "--xof=4"], b"abc");
+    assert_eq!(code, 1);
+}
@@ -188,3 +188,85 @@ fn blake2b_person() {
     ];
     assert_eq!(r, e);
 }
+
+#[test]
+fn blake2b_const() {
+    const R: [u8; 64] = blake2ya::blake2b_const(b"abc");
+    let e = [
+        0xba, 0x80, 0xa5, 0x3f, 0x98, 0x1c, 0x4d, 0x0d, 0x6a, 0x27, 0x97, 0xb6, 0x9f, 0x12, 0xf6, 0xe9, 0x4c, 0x21,
+        0x2f, 0x14, 0x68, 0x5a, 0xc4, 0xb7, 0x4b, 0x12, 0xbb, 0x6f, 0xdb, 0xff, 0xa2, 0xd1, 0x7d, 0x87, 0xc5, 0x39,
+        0x2a, 0xab, 0x79, 0x2d, 0xc2, 0x52, 0xd5, 0xde, 0x45, 0x33, 0xcc, 0x95, 0x18, 0xd3, 0x8a, 0xa8, 0xdb, 0xf1,
+        0x92, 0x5a, 0xb9, 0x23, 0x86, 0xed, 0xd4, 0x00, 0x99, 0x23,
+    ];
+    assert_eq!(R, e);
+    let m = (0..=255).cycle().take(300).collect::<Vec<u8>>();
+    for i in 0..m.len() {
+        let mut p = blake2ya::blake2b_params();
+        p.digest(32);
+        let mut h = blake2ya::blake2b(p);
+        h.update(&m[..i]);
+        let mut r = [0; 32];
+        h.digest(&mut r);
+        assert_eq!(r, blake2ya::blake2b_const::<32>(&m[..i]));
+    }
+}
+
+#[test]
+fn blake2b_long() {
+    let hex = |d: &[u8]| d.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    let mut r = [0; 97];
+    blake2ya::blake2b_long(&mut r[..1], &[b"abcdefghij"]);
+    assert_eq!(hex(&r[..1]), "50");
+    blake2ya::blake2b_long(&mut r[..65], &[b"abc", b"", b"defghij"]);
+    assert_eq!(
+        hex(&r[..65]),
+        "b21bb9d563ebb297ab3fb965ce81bc30bd72a42bae035dcd024f08ab27a4859209ea2a7b0fbf21eb202d4eda172eddbd0225daddb43a685e\
+         9890cb3f6f0ddd195c"
+    );
+    blake2ya::blake2b_long(&mut r, &[b"abcdefghij"]);
+    assert_eq!(
+        hex(&r),
+        "828b73cb43e7ee6d395c5e13cc788b7c80f887c4feb167c6d2f8733e9852b718b361802f6786c86815d44d683307c7dc65a09b5be108de\
+         82fc43a73d09a359038da1be34517d95609473960ecbcf637177fea90b6ff41d23ec3f49e262188d437e"
+    );
+    let mut r = [0; 1024];
+    blake2ya::blake2b_long(&mut r, &[b"abcde", b"fghij"]);
+    let mut p = blake2ya::blake2b_params();
+    p.digest(16);
+    let mut h = blake2ya::blake2b(p);
+    h.update(&r);
+    let mut d = [0; 16];
+    h.digest(&mut d);
+    assert_eq!(hex(&d), "4e11fb3ee9a66075650d4b1c59cc4244");
+}
+
+#[test]
+fn blake2b_compress() {
+    let hex = |h: &[u64; 8]| h.iter().flat_map(|w| w.to_le_bytes()).map(|b| format!("{:02x}", b)).collect::<String>();
+    let mut m = [0; 16];
+    m[0] = u64::from_le_bytes(*b"abc\0\0\0\0\0");
+    let mut iv = [
+        0x6a09e667f3bcc908,
+        0xbb67ae8584caa73b,
+        0x3c6ef372fe94f82b,
+        0xa54ff53a5f1d36f1,
+        0x510e527fade682d1,
+        0x9b05688c2b3e6c1f,
+        0x1f83d9abfb41bd6b,
+        0x5be0cd19137e2179,
+    ];
+    iv[0] ^= 0x01010040;
+    let f = [u64::MAX, 0];
+    let cases = [
+        (12, f, "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"),
+        (0, f, "08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b"),
+        (1, f, "b63a380cb2897d521994a85234ee2c181b5f844d2c624c002677e9703449d2fba551b3a8333bcdf5f2f7e08993d53923de3d64fcc68c034e717b9293fed7a421"),
+        (25, f, "09df5600d96313fe649dba417c7641180e74b10f9ce0135ec54cd409c521120619d322441e8e93bcc64605ff70c760dd1bafc089c8d81651c40fd9ad8b3bf50b"),
+        (12, [0, 0], "75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8d226039cd31b4e426ac4f2d3d666a610c2116fde4735"),
+    ];
+    for (rounds, f, e) in cases {
+        let mut h = iv;
+        blake2ya::blake2b_compress(&mut h, &m, &[3, 0], &f, rounds);
+        assert_eq!(hex(&h), e);
+    }
+}
@@ -0,0 +1,50 @@
+#[test]
+fn blake2bp_empty() {
+    let mut p = blake2ya::blake2b_params();
+    p.digest(64);
+    let mut h = blake2ya::blake2bp(p);
+    let mut r = [0; 64];
+    h.digest(&mut r);
+    let e = [
+        0xb5, 0xef, 0x81, 0x1a, 0x80, 0x38, 0xf7, 0x0b, 0x62, 0x8f, 0xa8, 0xb2, 0x94, 0xda, 0xae, 0x74, 0x92, 0xb1,
+        0xeb, 0xe3, 0x43, 0xa8, 0x0e, 0xaa, 0xbb, 0xf1, 0xf6, 0xae, 0x66, 0x4d, 0xd6, 0x7b, 0x9d, 0x90, 0xb0, 0x12,
+        0x07, 0x91, 0xea, 0xb8, 0x1d, 0xc9, 0x69, 0x85, 0xf2, 0x88, 0x49, 0xf6, 0xa3, 0x05, 0x18, 0x6a, 0x85, 0x50,
+        0x1b, 0x40, 0x51, 0x14, 0xbf, 0xa6, 0x78, 0xdf, 0x93, 0x80,
+    ];
+    assert_eq!(r, e);
+}
+
+#[test]
+fn blake2bp_digest_32() {
+    let mut p = blake2ya::blake2b_params();
+    p.digest(32);
+    let mut h = blake2ya::blake2bp(p);
+    h.update(b"abc");
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    let e = [
+        0x47, 0x92, 0xf0, 0x0c, 0x05, 0x82, 0x7a, 0x43, 0x7f, 0xc5, 0x54, 0x81, 0xe4, 0x47, 0xee, 0xa1, 0xc9, 0xa3,
+        0x9a, 0xdd, 0x28, 0x08, 0x77, 0x33, 0xb3, 0xe5, 0x3f, 0x1c, 0x04, 0x43, 0x0d, 0xc7,
+    ];
+    assert_eq!(r, e);
+}
+
+#[test]
+fn blake2bp_key() {
+    let mut p = blake2ya::blake2b_params();
+    p.digest(64);
+    p.key(&(0..64).collect::<Vec<u8>>());
+    let mut h = blake2ya::blake2bp(p);
+    let m = (0..255).collect::<Vec<u8>>();
+    h.update(&m[..100]);
+    h.update(&m[100..]);
+    let mut r = [0; 64];
+    h.digest(&mut r);
+    let e = [
+        0x96, 0xfb, 0xcb, 0xb6, 0x0b, 0xd3, 0x13, 0xb8, 0x84, 0x50, 0x33, 0xe5, 0xbc, 0x05, 0x8a, 0x38, 0x02, 0x74,
+        0x38, 0x57, 0x2d, 0x7e, 0x79, 0x57, 0xf3, 0x68, 0x4f, 0x62, 0x68, 0xaa, 0xdd, 0x3a, 0xd0, 0x8d, 0x21, 0x76,
+        0x7e, 0xd6, 0x87, 0x86, 0x85, 0x33, 0x1b, 0xa9, 0x85, 0x71, 0x48, 0x7e, 0x12, 0x47, 0x0a, 0xad, 0x66, 0x93,
+        0x26, 0x71, 0x6e, 0x46, 0x66, 0x7f, 0x69, 0xf8, 0xd7, 0xe8,
+    ];
+    assert_eq!(r, e);
+}
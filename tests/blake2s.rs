@@ -165,3 +165,38 @@ fn blake2s_person() {
     ];
     assert_eq!(r, e);
 }
+
+#[test]
+fn blake2s_const() {
+    const R: [u8; 32] = blake2ya::blake2s_const(b"abc");
+    let e = [
+        0x50, 0x8c, 0x5e, 0x8c, 0x32, 0x7c, 0x14, 0xe2, 0xe1, 0xa7, 0x2b, 0xa3, 0x4e, 0xeb, 0x45, 0x2f, 0x37, 0x45,
+        0x8b, 0x20, 0x9e, 0xd6, 0x3a, 0x29, 0x4d, 0x99, 0x9b, 0x4c, 0x86, 0x67, 0x59, 0x82,
+    ];
+    assert_eq!(R, e);
+    let m = (0..=255).cycle().take(300).collect::<Vec<u8>>();
+    for i in 0..m.len() {
+        let mut p = blake2ya::blake2s_params();
+        p.digest(16);
+        let mut h = blake2ya::blake2s(p);
+        h.update(&m[..i]);
+        let mut r = [0; 16];
+        h.digest(&mut r);
+        assert_eq!(r, blake2ya::blake2s_const::<16>(&m[..i]));
+    }
+}
+
+#[test]
+fn blake2s_compress() {
+    let hex = |h: &[u32; 8]| h.iter().flat_map(|w| w.to_le_bytes()).map(|b| format!("{:02x}", b)).collect::<String>();
+    let mut m = [0; 16];
+    m[0] = u32::from_le_bytes(*b"abc\0");
+    let mut iv = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+    iv[0] ^= 0x01010020;
+    let mut h = iv;
+    blake2ya::blake2s_compress(&mut h, &m, &[3, 0], &[u32::MAX, 0], 10);
+    assert_eq!(hex(&h), "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982");
+    let mut h = iv;
+    blake2ya::blake2s_compress(&mut h, &m, &[3, 0], &[u32::MAX, 0], 13);
+    assert_eq!(hex(&h), "efb30baf4a76271a555f61125c0cb1da021ca372ef085818aa015496c61cd98a");
+}
@@ -0,0 +1,43 @@
+#[test]
+fn blake2sp_empty() {
+    let mut p = blake2ya::blake2s_params();
+    p.digest(32);
+    let mut h = blake2ya::blake2sp(p);
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    let e = [
+        0xdd, 0x0e, 0x89, 0x17, 0x76, 0x93, 0x3f, 0x43, 0xc7, 0xd0, 0x32, 0xb0, 0x8a, 0x91, 0x7e, 0x25, 0x74, 0x1f,
+        0x8a, 0xa9, 0xa1, 0x2c, 0x12, 0xe1, 0xca, 0xc8, 0x80, 0x15, 0x00, 0xf2, 0xca, 0x4f,
+    ];
+    assert_eq!(r, e);
+}
+
+#[test]
+fn blake2sp_digest_16() {
+    let mut p = blake2ya::blake2s_params();
+    p.digest(16);
+    let mut h = blake2ya::blake2sp(p);
+    h.update(b"abc");
+    let mut r = [0; 16];
+    h.digest(&mut r);
+    let e = [0x88, 0xf5, 0x21, 0x43, 0xa5, 0xac, 0xf1, 0x07, 0x02, 0xb0, 0xa0, 0xde, 0x90, 0x53, 0x04, 0x72];
+    assert_eq!(r, e);
+}
+
+#[test]
+fn blake2sp_key() {
+    let mut p = blake2ya::blake2s_params();
+    p.digest(32);
+    p.key(&(0..32).collect::<Vec<u8>>());
+    let mut h = blake2ya::blake2sp(p);
+    let m = (0..255).collect::<Vec<u8>>();
+    h.update(&m[..100]);
+    h.update(&m[100..]);
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    let e = [
+        0x0c, 0x8a, 0x36, 0x59, 0x7d, 0x74, 0x61, 0xc6, 0x3a, 0x94, 0x73, 0x28, 0x21, 0xc9, 0x41, 0x85, 0x6c, 0x66,
+        0x83, 0x76, 0x60, 0x6c, 0x86, 0xa5, 0x2d, 0xe0, 0xee, 0x41, 0x04, 0xc6, 0x15, 0xdb,
+    ];
+    assert_eq!(r, e);
+}
@@ -0,0 +1,42 @@
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn blake2xb_abc() {
+    let mut h = blake2ya::blake2xb(blake2ya::blake2b_params(), 100);
+    h.update(b"abc");
+    let mut r = [0; 100];
+    h.digest(&mut r);
+    assert_eq!(
+        hex(&r),
+        "e0f82b71c07860b65be612d2633becc46596a6c12a8772b561adec35721b7a5c44a7e075e8a3bc8c4fc8390a197be2085b4aa4385c207f\
+         24e46415defc659afd73bacb288080b10849aeea386c60cd3fa04c9bcbfeebaed6e98634d696b9d5bdef0ad2c5"
+    );
+}
+
+#[test]
+fn blake2xb_unknown_length() {
+    let mut p = blake2ya::blake2b_params();
+    p.key(b"key");
+    p.salt(b"salt");
+    p.person(b"person");
+    let mut h = blake2ya::blake2xb(p, blake2ya::BLAKE2XB_UNKNOWN);
+    h.update(b"abc");
+    let mut r = [0; 70];
+    h.digest(&mut r);
+    assert_eq!(
+        hex(&r),
+        "1fb639550a39d1af46ec3b8cf66c250549f1d96ab305bd8c2c01e16222fd30cc9e4027e1ecccf5781d352f8d9a4cf8c179dce8eda750b9\
+         13b2f1bda9e4cf26a1287bcf90eb08"
+    );
+}
+
+#[test]
+fn blake2xb_length_in_output() {
+    let mut a = [0; 64];
+    let mut b = [0; 65];
+    blake2ya::blake2xb(blake2ya::blake2b_params(), 64).digest(&mut a);
+    blake2ya::blake2xb(blake2ya::blake2b_params(), 65).digest(&mut b);
+    assert_ne!(a, b[..64]);
+}
@@ -0,0 +1,28 @@
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn blake2xs_abc() {
+    let mut h = blake2ya::blake2xs(blake2ya::blake2s_params(), 50);
+    h.update(b"abc");
+    let mut r = [0; 50];
+    h.digest(&mut r);
+    assert_eq!(
+        hex(&r),
+        "3fbda980e22b1efa091320b6b802a41ed42f6017b56c4f9c7c5928facc12e95efdd10ec995d157f202caacbf63f1a0de9504"
+    );
+}
+
+#[test]
+fn blake2xs_unknown_length() {
+    let mut p = blake2ya::blake2s_params();
+    p.key(b"key");
+    p.salt(b"salt");
+    p.person(b"person");
+    let mut h = blake2ya::blake2xs(p, blake2ya::BLAKE2XS_UNKNOWN);
+    h.update(b"abc");
+    let mut r = [0; 40];
+    h.digest(&mut r);
+    assert_eq!(hex(&r), "303d3e276044cca324a2eaa3f204305955dd8968a0156b90ac3735e6f3b190030d4eeb96e68ad777");
+}
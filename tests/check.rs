@@ -0,0 +1,73 @@
+#![cfg(feature = "std")]
+
+use blake2ya::Algorithm;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn check_parse_gnu() {
+    let e = blake2ya::parse_line(b"aa4938119b1dc7b87cbad0ffd200d0ae *a b", Algorithm::Blake2s).unwrap();
+    assert_eq!(e.algorithm, Algorithm::Blake2s);
+    assert_eq!(e.length, 16);
+    assert_eq!(e.path, Path::new("a b"));
+    assert_eq!(e.digest[..2], [0xaa, 0x49]);
+    let e = blake2ya::parse_line(b"\\AABB  x\\\\y\\nz", Algorithm::Blake2b).unwrap();
+    assert_eq!((e.length, e.path), (2, PathBuf::from("x\\y\nz")));
+    assert!(blake2ya::parse_line(b"abc  x", Algorithm::Blake2b).is_none());
+    assert!(blake2ya::parse_line(b"aabg  x", Algorithm::Blake2b).is_none());
+    assert!(blake2ya::parse_line(&[b"aa".repeat(33), b"  x".to_vec()].concat(), Algorithm::Blake2s).is_none());
+    assert!(blake2ya::parse_line(b"aabb  ", Algorithm::Blake2b).is_none());
+}
+
+#[test]
+fn check_parse_bsd() {
+    let e = blake2ya::parse_line(b"BLAKE2sp-16 (f (1)) = aabb", Algorithm::Blake2b).unwrap();
+    assert_eq!(e.algorithm, Algorithm::Blake2sp);
+    assert_eq!((e.length, e.path, e.digest), (2, PathBuf::from("f (1)"), vec![0xaa, 0xbb]));
+    let e = blake2ya::parse_line(b"BLAKE2b (f) = aabbcc", Algorithm::Blake2s).unwrap();
+    assert_eq!((e.algorithm, e.length), (Algorithm::Blake2b, 3));
+    assert!(blake2ya::parse_line(b"BLAKE2b-32 (f) = aabb", Algorithm::Blake2b).is_none());
+    assert!(blake2ya::parse_line(b"BLAKE2x (f) = aabb", Algorithm::Blake2b).is_none());
+}
+
+#[test]
+fn check_run() {
+    let d = std::env::temp_dir().join(format!("blake2ya-check-{}", std::process::id()));
+    std::fs::create_dir_all(&d).unwrap();
+    std::fs::write(d.join("abc"), b"abc").unwrap();
+    let abc = d.join("abc").display().to_string();
+    let nope = d.join("nope").display().to_string();
+    let manifest = format!(
+        "# comment\n508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982  {abc}\n\
+         BLAKE2s-128 ({abc}) = aa4938119b1dc7b87cbad0ffd200d0af\nbad line\n00  {nope}\n"
+    );
+    let mut c = blake2ya::check();
+    c.algorithm(Algorithm::Blake2s);
+    let mut out = vec![];
+    let mut warned = vec![];
+    let s = c.run(manifest.as_bytes(), &mut out, |p, _| warned.push(p.to_path_buf())).unwrap();
+    assert_eq!((s.ok, s.failed, s.missing, s.ignored, s.malformed), (1, 1, 1, 0, 1));
+    assert!(!s.success());
+    assert_eq!(String::from_utf8(out).unwrap(), format!("{abc}: OK\n{abc}: FAILED\n{nope}: FAILED open or read\n"));
+    assert_eq!(warned, [d.join("nope")]);
+
+    let manifest = format!("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982  {abc}\nx\n00  {nope}\n");
+    c.quiet(true);
+    c.ignore_missing(true);
+    let mut out = vec![];
+    let s = c.run(manifest.as_bytes(), &mut out, |_, _| panic!()).unwrap();
+    assert_eq!((s.ok, s.ignored, s.malformed), (1, 1, 1));
+    assert!(out.is_empty());
+    assert!(s.success());
+    c.strict(true);
+    assert!(!c.run(manifest.as_bytes(), std::io::sink(), |_, _| ()).unwrap().success());
+    std::fs::remove_dir_all(&d).unwrap();
+}
+
+#[test]
+fn check_escape() {
+    // Reports escape backslashes as b2sum does, so that they read like the checksum line.
+    let mut out = vec![];
+    let s = blake2ya::check().run(&b"\\00  no\\\\such\\rfile\n"[..], &mut out, |_, _| ()).unwrap();
+    assert_eq!(s.missing, 1);
+    assert_eq!(out, b"\\no\\\\such\\rfile: FAILED open or read\n");
+}
@@ -0,0 +1,55 @@
+//! Test vectors of EIP-152. All of them hash "abc" with the parameter block of BLAKE2b-512.
+
+const HMT: &str = "48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fb\
+                   abd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000\
+                   00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000\
+                   00000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000";
+
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+fn input(rounds: u32, f: u8) -> Vec<u8> {
+    unhex(&format!("{:08x}{}{:02x}", rounds, HMT, f))
+}
+
+#[test]
+fn eip152_errors() {
+    use blake2ya::Eip152Error;
+    let i = input(12, 1);
+    assert_eq!(blake2ya::eip152_blake2_f(&[]), Err(Eip152Error::Length));
+    assert_eq!(blake2ya::eip152_blake2_f(&i[1..]), Err(Eip152Error::Length));
+    assert_eq!(blake2ya::eip152_blake2_f(&[&i[..], &[1]].concat()), Err(Eip152Error::Length));
+    assert_eq!(blake2ya::eip152_blake2_f(&input(12, 2)), Err(Eip152Error::FinalFlag));
+    assert_eq!(blake2ya::eip152_gas(&i[1..]), 0);
+}
+
+#[test]
+fn eip152_vectors() {
+    let cases = [
+        (0, 1, "08c9bcf367e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d282e6ad7f520e511f6c3e2b8c68059b9442be0454267ce079217e1319cde05b"),
+        (12, 1, "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"),
+        (12, 0, "75ab69d3190a562c51aef8d88f1c2775876944407270c42c9844252c26d2875298743e7f6d5ea2f2d3e8d226039cd31b4e426ac4f2d3d666a610c2116fde4735"),
+        (1, 1, "b63a380cb2897d521994a85234ee2c181b5f844d2c624c002677e9703449d2fba551b3a8333bcdf5f2f7e08993d53923de3d64fcc68c034e717b9293fed7a421"),
+    ];
+    for (rounds, f, e) in cases {
+        let i = input(rounds, f);
+        assert_eq!(blake2ya::eip152_gas(&i), rounds as u64);
+        assert_eq!(hex(&blake2ya::eip152_blake2_f(&i).unwrap()), e);
+    }
+}
+
+#[test]
+#[ignore = "2^32 - 1 rounds take minutes"]
+fn eip152_max_rounds() {
+    let i = input(u32::MAX, 1);
+    assert_eq!(blake2ya::eip152_gas(&i), u32::MAX as u64);
+    assert_eq!(
+        hex(&blake2ya::eip152_blake2_f(&i).unwrap()),
+        "fc59093aafa9ab43daae0e914c57635c5402d8e3d2130eb9b3cc181de7f0ecf9b22bf99a7815ce16419e200e01846e6b5df8cc7703041bbceb571de6631d2615"
+    );
+}
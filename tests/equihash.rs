@@ -0,0 +1,58 @@
+//! Vectors of the Zcash test suite, from the equihash crate (MIT or Apache-2.0): the kind, n, k, input, nonce and
+//! indices of each solution.
+
+use blake2ya::EquihashError;
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+/// The minimal encoding of indices: n / (k + 1) + 1 big-endian bits each.
+fn minimal(idx: &[u32], w: usize) -> Vec<u8> {
+    let mut r = vec![0; idx.len() * w / 8];
+    for (j, &x) in idx.iter().enumerate() {
+        for b in 0..w {
+            let p = j * w + b;
+            r[p / 8] |= ((x >> (w - 1 - b) & 1) as u8) << (7 - p % 8);
+        }
+    }
+    r
+}
+
+#[test]
+fn equihash_vectors() {
+    for line in include_str!("data/equihash.txt").lines() {
+        let f: Vec<&str> = line.split(' ').collect();
+        let n: u32 = f[1].parse().unwrap();
+        let k: u32 = f[2].parse().unwrap();
+        let idx: Vec<u32> = f[5].split(',').map(|x| x.parse().unwrap()).collect();
+        let mut e = blake2ya::equihash(n, k);
+        e.update(&unhex(f[3]));
+        e.update(&unhex(f[4]));
+        let want = match f[0] {
+            "ok" => Ok(()),
+            "Collision" => Err(EquihashError::Collision),
+            "OutOfOrder" => Err(EquihashError::Order),
+            "DuplicateIdxs" => Err(EquihashError::Duplicate),
+            "NonZeroRootHash" => Err(EquihashError::NonZero),
+            _ => unreachable!(),
+        };
+        assert_eq!(e.verify_indices(&idx), want, "{}", line);
+        let s = minimal(&idx, (n / (k + 1) + 1) as usize);
+        assert_eq!(s.len(), e.solution_len());
+        assert_eq!(e.verify(&s), want, "{}", line);
+    }
+}
+
+#[test]
+fn equihash_errors() {
+    let e = blake2ya::equihash(200, 9);
+    assert_eq!(e.solution_len(), 1344);
+    assert_eq!(e.verify(&[0; 1343]), Err(EquihashError::Length));
+    assert_eq!(e.verify_indices(&[0; 256]), Err(EquihashError::Length));
+    let e = blake2ya::equihash(96, 5);
+    // Equal indices always collide, and are caught as duplicates.
+    assert_eq!(e.verify_indices(&[7; 32]), Err(EquihashError::Duplicate));
+    let idx: Vec<u32> = (0..32).collect();
+    assert_eq!(e.verify_indices(&idx), Err(EquihashError::Collision));
+}
@@ -0,0 +1,40 @@
+use core::hash::{BuildHasher, Hasher};
+
+#[test]
+fn hasher_write() {
+    let k = (0..32).collect::<Vec<u8>>();
+    let mut h = blake2ya::Blake2sHasher::new(&k);
+    h.write(b"a");
+    h.write(b"bc");
+    assert_eq!(h.finish(), 0xc80ad38143b4c4b2);
+    assert_eq!(h.finish(), 0xc80ad38143b4c4b2);
+}
+
+#[test]
+fn hasher_write_int() {
+    let k = (0..32).collect::<Vec<u8>>();
+    let mut h = blake2ya::Blake2sHasher::new(&k);
+    h.write_u64(42);
+    h.write_u8(0xff);
+    assert_eq!(h.finish(), 0x60d5066fc451da4c);
+}
+
+#[test]
+fn hasher_build_with_key() {
+    let s = blake2ya::RandomBlake2sState::with_key(&(0..32).collect::<Vec<u8>>());
+    assert_eq!(s.hash_one(b"abc"), s.hash_one(b"abc"));
+    assert_ne!(s.hash_one(b"abc"), s.hash_one(b"abd"));
+    assert_ne!(s.hash_one(b"abc"), blake2ya::RandomBlake2sState::with_key(b"").hash_one(b"abc"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hasher_build_random() {
+    let s = blake2ya::RandomBlake2sState::new();
+    assert_eq!(s.hash_one(b"abc"), blake2ya::RandomBlake2sState::default().hash_one(b"abc"));
+    let mut m = std::collections::HashMap::with_hasher(s);
+    m.insert("abc", 1);
+    m.insert("abd", 2);
+    assert_eq!(m["abc"], 1);
+    assert_eq!(m["abd"], 2);
+}
@@ -0,0 +1,85 @@
+//! Vectors from Python's hmac and hashlib.pbkdf2_hmac, and HKDF of RFC 5869 written on top of hmac.
+
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn hmac_blake2b() {
+    let mut h = blake2ya::hmac_blake2b(b"key");
+    h.update(b"The quick brown fox ");
+    h.update(b"jumps over the lazy dog");
+    let mut r = [0; 64];
+    h.digest(&mut r);
+    assert_eq!(
+        hex(&r),
+        "92294f92c0dfb9b00ec9ae8bd94d7e7d8a036b885a499f149dfe2fd2199394aaaf6b8894a1730cccb2cd050f9bcf5062a38b51b0dab33207f8ef35ae2c9df51b"
+    );
+    let mut h = blake2ya::hmac_blake2b(&(0..200).collect::<Vec<u8>>());
+    h.update(b"abc");
+    h.digest(&mut r);
+    assert_eq!(
+        hex(&r),
+        "feb09eb5b1c557085c0a53bdf39ef7bc9af291f21d7c917cd1cf09542aab95362de79b3925fe55d92997423b5a68be1bda2f6518df34fa1053bb3ef559b08200"
+    );
+}
+
+#[test]
+fn hmac_blake2s() {
+    let mut h = blake2ya::hmac_blake2s(b"key");
+    h.update(b"The quick brown fox jumps over the lazy dog");
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    assert_eq!(hex(&r), "f93215bb90d4af4c3061cd932fb169fb8bb8a91d0b4022baea1271e1323cd9a0");
+    let mut h = blake2ya::hmac_blake2s(&(0..100).collect::<Vec<u8>>());
+    h.update(b"abc");
+    h.digest(&mut r);
+    assert_eq!(hex(&r), "c1b751501db12b753069a918627b623179be55c8cc7ca5c4e14bdf32274ac656");
+}
+
+#[test]
+fn hkdf_blake2b() {
+    let salt = (0..13).collect::<Vec<u8>>();
+    let info = (0xf0..0xfa).collect::<Vec<u8>>();
+    let prk = blake2ya::hkdf_blake2b_extract(&salt, &[0x0b; 22]);
+    assert_eq!(
+        hex(&prk),
+        "02fbaa4ced1e659fe2eb8ae358de5be0edc0fd4526dbc7cc68d2ab9273e1b230ab9d6860f65dc7bad92a483c0f90e019ace68b5e4fe65251666eb1e71e57a812"
+    );
+    let mut okm = [0; 100];
+    blake2ya::hkdf_blake2b_expand(&prk, &info, &mut okm);
+    assert_eq!(
+        hex(&okm),
+        "8815e1a85b5e90e6174323fdd180248887a7138af6dc5c8320fde21a60a078808267d6a41b6a938d7b30b3c3a7a0aef45b64cf11b2d32e47f4\
+         6eb5494fbf2a85f051c1a1181469c1d6698dc722beeb3a0c5f973b29f43c79a34a5c303547a151c151228e"
+    );
+}
+
+#[test]
+fn hkdf_blake2s() {
+    let salt = (0..13).collect::<Vec<u8>>();
+    let info = (0xf0..0xfa).collect::<Vec<u8>>();
+    let prk = blake2ya::hkdf_blake2s_extract(&salt, &[0x0b; 22]);
+    assert_eq!(hex(&prk), "57e878130679f9ea85900980b52df2643d043b82f290eb7dd62175dbb04cca4e");
+    let mut okm = [0; 42];
+    blake2ya::hkdf_blake2s_expand(&prk, &info, &mut okm);
+    assert_eq!(hex(&okm), "1472c31f2ff768c71b19f8803683ee3b13c1a5fb3ea59c0c3bf0d44a4a40dcd4329d9cd85bbe35a1b3e7");
+}
+
+#[test]
+fn pbkdf2_blake2b() {
+    let mut r = [0; 100];
+    blake2ya::pbkdf2_blake2b(b"password", b"salt", 4096, &mut r);
+    assert_eq!(
+        hex(&r),
+        "9d4f324ef40b5be658fa0ab94a168664f060c0c9cc85a02ac83f2d44088cb7e7b812ef60e9b1673d4fd77240a68607d72b912e18a0ea4772f4\
+         76be7583b66970c04c8f9fda5beb0d0627151e80da25703eee220d80b19694bcac777bdd3e213c51a3fc02"
+    );
+}
+
+#[test]
+fn pbkdf2_blake2s() {
+    let mut r = [0; 40];
+    blake2ya::pbkdf2_blake2s(b"password", b"salt", 4096, &mut r);
+    assert_eq!(hex(&r), "072b63e2cfe4d20cd2086a6be6ec8e1fd1bf2b797fa272a749a761faad66beb61c8071a4aae2fe3e");
+}
@@ -0,0 +1,136 @@
+//! The known answer tests of the BLAKE2 reference package, in tests/data/blake2-kat.h as shipped unmodified with libb2
+//! 0.96. Inputs are the bytes 0, 1, 2, ... of every length in [0, 256), unkeyed and keyed with the bytes 0, 1, 2, ...
+//! of the maximal key length. The header covers BLAKE2b, BLAKE2bp, BLAKE2s and BLAKE2sp; it predates the BLAKE2X
+//! entries of the upstream blake2-kat.json. Those follow the same pattern for every output length in [1, 256] of the
+//! 256-byte input, and are computed here by the reference BLAKE2X code of fixtures/blake2-ref.
+
+struct Kat {
+    hash: String,
+    data: Vec<u8>,
+    key: Vec<u8>,
+    out: Vec<u8>,
+}
+
+/// Read the vectors of the arrays `<hash>_kat` and `<hash>_keyed_kat`, of one braced row of hex bytes per input length.
+fn kats() -> Vec<Kat> {
+    let mut r = vec![];
+    let mut name = None;
+    let mut row = vec![];
+    for line in include_str!("data/blake2-kat.h").lines() {
+        let line = line.trim();
+        if let Some(x) = line.strip_prefix("static const uint8_t ") {
+            name = Some(x.split('[').next().unwrap().to_string());
+        } else if line == "};" {
+            name = None;
+        } else if let Some(n) = &name {
+            row.extend(
+                line.split(',').filter_map(|x| x.trim().strip_prefix("0x")).map(|x| u8::from_str_radix(x, 16).unwrap()),
+            );
+            if line.starts_with('}') && !row.is_empty() {
+                let hash = n.trim_end_matches("_kat").trim_end_matches("_keyed");
+                let keylen = if n.ends_with("_keyed_kat") { row.len() } else { 0 };
+                let i = r.iter().filter(|k: &&Kat| k.hash == hash && k.key.len() == keylen).count();
+                r.push(Kat {
+                    hash: hash.to_string(),
+                    data: (0..i as u8).collect(),
+                    key: (0..keylen as u8).collect(),
+                    out: std::mem::take(&mut row),
+                });
+            }
+        }
+    }
+    r
+}
+
+/// Check the 512 vectors of one variant.
+fn check(hash: &str) {
+    let kats = kats().into_iter().filter(|k| k.hash == hash).collect::<Vec<_>>();
+    assert_eq!(kats.len(), 512);
+    for k in &kats {
+        let mut r = vec![0; k.out.len()];
+        match k.hash.as_str() {
+            "blake2b" | "blake2bp" => {
+                let mut p = blake2ya::blake2b_params();
+                p.digest(k.out.len() as u8);
+                p.key(&k.key);
+                if k.hash == "blake2b" {
+                    let mut h = blake2ya::blake2b(p);
+                    h.update(&k.data);
+                    h.digest(&mut r);
+                } else {
+                    let mut h = blake2ya::blake2bp(p);
+                    h.update(&k.data);
+                    h.digest(&mut r);
+                }
+            }
+            "blake2s" | "blake2sp" => {
+                let mut p = blake2ya::blake2s_params();
+                p.digest(k.out.len() as u8);
+                p.key(&k.key);
+                if k.hash == "blake2s" {
+                    let mut h = blake2ya::blake2s(p);
+                    h.update(&k.data);
+                    h.digest(&mut r);
+                } else {
+                    let mut h = blake2ya::blake2sp(p);
+                    h.update(&k.data);
+                    h.digest(&mut r);
+                }
+            }
+            h => panic!("unknown hash {}", h),
+        }
+        assert_eq!(r, k.out, "{} of {} bytes, key of {} bytes", k.hash, k.data.len(), k.key.len());
+    }
+}
+
+#[test]
+fn kat_blake2b() {
+    check("blake2b");
+}
+
+#[test]
+fn kat_blake2bp() {
+    check("blake2bp");
+}
+
+#[test]
+fn kat_blake2s() {
+    check("blake2s");
+}
+
+#[test]
+fn kat_blake2sp() {
+    check("blake2sp");
+}
+
+#[test]
+fn kat_blake2xb() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for key in [&[][..], &data[..64]] {
+        for n in 1..=256 {
+            let mut p = blake2ya::blake2b_params();
+            p.key(key);
+            let mut h = blake2ya::blake2xb(p, n as u32);
+            h.update(&data);
+            let mut r = vec![0; n];
+            h.digest(&mut r);
+            assert_eq!(r, blake2_ref::blake2xb(n as u32, n, &data, key), "{} bytes, key of {} bytes", n, key.len());
+        }
+    }
+}
+
+#[test]
+fn kat_blake2xs() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for key in [&[][..], &data[..32]] {
+        for n in 1..=256 {
+            let mut p = blake2ya::blake2s_params();
+            p.key(key);
+            let mut h = blake2ya::blake2xs(p, n as u16);
+            h.update(&data);
+            let mut r = vec![0; n];
+            h.digest(&mut r);
+            assert_eq!(r, blake2_ref::blake2xs(n as u16, n, &data, key), "{} bytes, key of {} bytes", n, key.len());
+        }
+    }
+}
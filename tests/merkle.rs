@@ -0,0 +1,100 @@
+//! Roots and proofs checked against a Python model of the RFC 6962 tree hash over BLAKE2b-256.
+
+use blake2ya::{MerkleError, MerkleLayout};
+
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+const ROOT: &str = "745d526a3d4b19128b495f003f46661ca211c00206001342bfa06c647d3bde9a";
+
+#[test]
+fn merkle_verify() {
+    let m = blake2ya::merkle(MerkleLayout::Rfc6962);
+    let root = unhex(ROOT);
+    // Leaf 5 of leaf0 to leaf6: leaf 4, leaf 6 and the root of leaves 0 to 3.
+    let path = unhex(concat!(
+        "480498d7ddfec17ac3185fecd843d0ec9cbdc64c3c40f9a336fab5311a87e619",
+        "e09fccb60c07e72ce261b16089095d3d71cd8313f004a98f837a4474ee854000",
+        "669873f4ac16ced0ad0ee074cc4c61df2f3dfc7cf6bf95fb75d631c8af8f2228",
+    ));
+    assert_eq!(m.verify(&root, b"leaf5", 5, 7, &path), Ok(()));
+    assert_eq!(m.verify(&root, b"leaf4", 5, 7, &path), Err(MerkleError::Root));
+    assert_eq!(m.verify(&root, b"leaf5", 4, 7, &path), Err(MerkleError::Root));
+    assert_eq!(m.verify(&root, b"leaf5", 7, 7, &path), Err(MerkleError::Index));
+    assert_eq!(m.verify(&root, b"leaf5", 5, 7, &path[..64]), Err(MerkleError::Length));
+    assert_eq!(m.verify(&root, b"leaf5", 5, 7, &path[..95]), Err(MerkleError::Length));
+    assert_eq!(m.verify(&root, b"leaf5", 5, 6, &path), Err(MerkleError::Length));
+    // Leaves 4 and 5: leaf 6, then the root of leaves 0 to 3 first in depth-first order.
+    let proof = [&path[64..], &path[32..64]].concat();
+    assert_eq!(m.verify_multi(&root, &[(4, b"leaf4"), (5, b"leaf5")], 7, &proof), Ok(()));
+    assert_eq!(m.verify_multi(&root, &[(5, b"leaf5"), (4, b"leaf4")], 7, &proof), Err(MerkleError::Index));
+    assert_eq!(m.verify_multi(&root, &[(4, b"leaf4"), (5, b"leaf5")], 7, &path), Err(MerkleError::Length));
+
+    let mut r = [0; 32];
+    m.empty(&mut r);
+    assert_eq!(hex(&r), "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8");
+    blake2ya::merkle(MerkleLayout::Sia).empty(&mut r);
+    assert_eq!(r, [0; 32]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn merkle_tree() {
+    let leaves: Vec<Vec<u8>> = (0..7).map(|i| format!("leaf{}", i).into_bytes()).collect();
+    let t = blake2ya::merkle(MerkleLayout::Rfc6962).tree(&leaves);
+    assert_eq!(hex(&t.root()), ROOT);
+    let mut m = blake2ya::merkle(MerkleLayout::Rfc6962);
+    m.person(b"leaf", b"node");
+    let t = m.tree(&leaves);
+    assert_eq!(hex(&t.root()), "d68c70e0996928ab6f52fd881eef2835c5034771b97ec6930bae94d88a544ca2");
+    assert_eq!(m.verify(&t.root(), b"leaf3", 3, 7, &t.proof(3)), Ok(()));
+    let t = blake2ya::merkle(MerkleLayout::Sia).tree(Vec::<Vec<u8>>::new());
+    assert_eq!(t.size(), 0);
+    assert_eq!(t.root(), [0; 32]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn merkle_proofs() {
+    for n in [32, 20] {
+        let mut m = blake2ya::merkle(MerkleLayout::Sia);
+        m.digest(n);
+        for size in 1..=33u64 {
+            let leaves: Vec<Vec<u8>> = (0..size).map(|i| i.to_le_bytes().to_vec()).collect();
+            let t = m.tree(&leaves);
+            let root = t.root();
+            assert_eq!(root.len(), n as usize);
+            for i in 0..size {
+                let p = t.proof(i);
+                assert_eq!(m.verify(&root, &leaves[i as usize], i, size, &p), Ok(()));
+                if !p.is_empty() {
+                    let mut q = p.clone();
+                    q[0] ^= 1;
+                    assert_eq!(m.verify(&root, &leaves[i as usize], i, size, &q), Err(MerkleError::Root));
+                }
+            }
+            // Every run of indices and every other index.
+            for a in 0..size {
+                for step in [1, 2, 5] {
+                    let idx: Vec<u64> = (a..size).step_by(step).collect();
+                    let l: Vec<(u64, &[u8])> = idx.iter().map(|&i| (i, &leaves[i as usize][..])).collect();
+                    let p = t.proof_multi(&idx);
+                    assert_eq!(m.verify_multi(&root, &l, size, &p), Ok(()));
+                    if idx.len() == 1 {
+                        assert_eq!(p.len(), t.proof(a).len());
+                    }
+                    if !p.is_empty() {
+                        let mut q = p.clone();
+                        q[p.len() - 1] ^= 1;
+                        assert_eq!(m.verify_multi(&root, &l, size, &q), Err(MerkleError::Root));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Roots and proofs of a Grin PMMR over the elements [0, 0, 0, i + 1] of the Grin test suite, four big-endian u32 each,
+//! as computed by grin_core 5.5.2.
+
+use blake2ya::{MmrError, MmrProof};
+
+fn unhex(s: &str) -> [u8; 32] {
+    core::array::from_fn(|i| u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap())
+}
+
+fn leaf(i: u64) -> [u8; 16] {
+    let mut r = [0; 16];
+    r[12..].copy_from_slice(&(i as u32 + 1).to_be_bytes());
+    r
+}
+
+/// Size, leaf index, position and path of each proof.
+fn proofs() -> Vec<(u64, u64, u64, Vec<[u8; 32]>)> {
+    let data = include_str!("data/mmr.txt");
+    let f = |l: &str| l.split(' ').map(String::from).collect::<Vec<_>>();
+    data.lines()
+        .filter(|l| l.starts_with("proof"))
+        .map(f)
+        .map(|f| {
+            (f[1].parse().unwrap(), f[2].parse().unwrap(), f[3].parse().unwrap(), f[4].split(',').map(unhex).collect())
+        })
+        .collect()
+}
+
+fn roots() -> Vec<(u64, [u8; 32])> {
+    let data = include_str!("data/mmr.txt");
+    data.lines()
+        .filter(|l| l.starts_with("root"))
+        .map(|l| l.split(' ').collect::<Vec<_>>())
+        .map(|f| (f[1].parse().unwrap(), unhex(f[2])))
+        .collect()
+}
+
+#[test]
+fn mmr_verify() {
+    let root = roots().into_iter().find(|r| r.0 == 19).unwrap().1;
+    for (size, i, pos, path) in proofs() {
+        let p = MmrProof::new(size, &path).unwrap();
+        assert_eq!(p.verify(&root, &leaf(i), pos), Ok(()));
+        assert_eq!(p.verify(&root, &leaf(i + 1), pos), Err(MmrError::Root));
+    }
+    let p = MmrProof::new(19, &proofs()[0].3).unwrap();
+    assert_eq!(p.verify(&root, &leaf(0), 2), Err(MmrError::Position));
+    assert_eq!(p.verify(&root, &leaf(0), 19), Err(MmrError::Position));
+    let p = MmrProof::new(20, &[]).unwrap();
+    assert_eq!(p.verify(&root, &leaf(0), 0), Err(MmrError::Size));
+    assert_eq!(MmrProof::new(19, &[[0; 32]; 65]).err(), Some(MmrError::Length));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn mmr_grin() {
+    let mut m = blake2ya::mmr(blake2ya::MmrMemory::default(), 0).unwrap();
+    assert_eq!(m.root(), Ok([0; 32]));
+    for (i, (size, root)) in roots().into_iter().enumerate() {
+        m.push(&leaf(i as u64)).unwrap();
+        assert_eq!(m.size(), size);
+        assert_eq!(m.leaves(), i as u64 + 1);
+        assert_eq!(m.root(), Ok(root));
+    }
+    let mut m = blake2ya::mmr(blake2ya::MmrMemory::default(), 0).unwrap();
+    for i in 0..11 {
+        assert_eq!(m.push(&leaf(i)).unwrap(), [0, 1, 3, 4, 7, 8, 10, 11, 15, 16, 18][i as usize]);
+    }
+    for (size, _, pos, path) in proofs() {
+        let p = m.proof(pos).unwrap();
+        assert_eq!(p.size(), size);
+        assert_eq!(p.path(), path);
+    }
+    assert_eq!(m.proof(2).err(), Some(MmrError::Position));
+    assert_eq!(m.proof(19).err(), Some(MmrError::Position));
+    assert_eq!(blake2ya::mmr(blake2ya::MmrMemory::default(), 2).err(), Some(MmrError::Size));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn mmr_prune() {
+    let mut m = blake2ya::mmr(blake2ya::MmrMemory::default(), 0).unwrap();
+    let pos: Vec<u64> = (0..11).map(|i| m.push(&leaf(i)).unwrap()).collect();
+    let root = m.root().unwrap();
+    assert_eq!(m.backend().len(), 19);
+    // Leaves 0 to 3 make the first half of the peak at 14; 4 and 5 make the node at 9.
+    for &p in &pos[..4] {
+        m.backend_mut().prune(p).unwrap();
+    }
+    m.backend_mut().prune(pos[5]).unwrap();
+    assert_eq!(m.backend().len(), 13);
+    assert_eq!(m.root(), Ok(root));
+    assert_eq!(m.proof(pos[1]).err(), Some(MmrError::Missing(0)));
+    for (i, &p) in pos.iter().enumerate().skip(4) {
+        m.proof(p).unwrap().verify(&root, &leaf(i as u64), p).unwrap();
+    }
+    // Appending after pruning only needs the peaks.
+    m.push(&leaf(11)).unwrap();
+    let mut n = blake2ya::mmr(blake2ya::MmrMemory::default(), 0).unwrap();
+    (0..12).for_each(|i| _ = n.push(&leaf(i)).unwrap());
+    assert_eq!(m.root(), n.root());
+    assert_eq!(m.backend_mut().prune(2), Err(MmrError::Position));
+}
@@ -0,0 +1,57 @@
+//! Vectors from a Python transcription of the Noise specification on top of hashlib and hmac.
+
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn noise_wireguard_initial_state() {
+    // Construction and identifier of WireGuard, whose chaining key and hash are constants of its implementations.
+    let mut s = blake2ya::SymmetricState2s::new(b"Noise_IKpsk2_25519_ChaChaPoly_BLAKE2s");
+    assert_eq!(hex(s.handshake_hash()), "60e26daef327efc02ec335e2a025d2d016eb4206f87277f52d38d1988b78cd36");
+    s.mix_hash(b"WireGuard v1 zx2c4 Jason@zx2c4.com");
+    assert_eq!(hex(s.handshake_hash()), "2211b361081ac566691243db458ad5322d9c6c662293e8b70ee19c65ba079ef3");
+    assert_eq!(s.cipher_key(), None);
+}
+
+#[test]
+fn noise_symmetric_state_2s() {
+    let mut s = blake2ya::SymmetricState2s::new(b"Noise_IKpsk2_25519_ChaChaPoly_BLAKE2s");
+    s.mix_hash(b"prologue");
+    s.mix_key(&(0..32).collect::<Vec<u8>>());
+    assert_eq!(hex(s.cipher_key().unwrap()), "203b22d63d63e35cebb9e40210cf8c1d7f90879428a4c766cda9ef34bb299398");
+    s.mix_hash(b"ciphertext");
+    s.mix_key_and_hash(&[7; 32]);
+    assert_eq!(hex(s.cipher_key().unwrap()), "8fe35ef0eeb9dc3f3d0e3e7ebdbfadc02892020a5940217d2c47419d5c56503c");
+    assert_eq!(hex(s.handshake_hash()), "3bb2ce201f862cd45492bb4fa93280989c5a4e35cb527eb08a68a7099ad35b59");
+    let (a, b) = s.split();
+    assert_eq!(hex(&a), "002cfae760b8c3614c78bdd8f9ef404203a0f1ecd9b706bd68df2f5f077d2ed6");
+    assert_eq!(hex(&b), "4062b2fd0d4b9592675b27118be6fb37a183af075fb8a9488d14625367feaba4");
+}
+
+#[test]
+fn noise_symmetric_state_2b() {
+    let mut s = blake2ya::SymmetricState2b::new(b"Noise_IK_25519_ChaChaPoly_BLAKE2b");
+    assert_eq!(&s.handshake_hash()[..33], b"Noise_IK_25519_ChaChaPoly_BLAKE2b");
+    s.mix_hash(b"prologue");
+    s.mix_key(&(0..32).collect::<Vec<u8>>());
+    assert_eq!(hex(s.cipher_key().unwrap()), "ba5af4812bad044c8c6a58536551db10562f8f57705f1c1bfb08bc7b044205ec");
+    s.mix_hash(b"ciphertext");
+    s.mix_key_and_hash(&[7; 32]);
+    assert_eq!(hex(s.cipher_key().unwrap()), "1b762f19f43027b913d3b7f3fb72f23197836482079316683485c7028bbae101");
+    assert_eq!(
+        hex(s.handshake_hash()),
+        "e5fc942e134b83bece0a98ecad88ebad2f3620c86bbb8c808173dadf39eaae2dcaa9ba1f6c6dc4c3d672989bda5d4525c8a18289a0a0a7bd8618ebdea0b7eb54"
+    );
+    let (a, b) = s.split();
+    assert_eq!(hex(&a), "da6949570a56ab2ac392f29ac4fc142748d5561be2e7032d6a29e466e0d1db8f");
+    assert_eq!(hex(&b), "764f2d108b1fa811ad6f3550f2f1d5c435ad4f79a122cd021c52841a9068dbc1");
+}
+
+#[test]
+fn noise_hkdf_prefix() {
+    let (a, b) = blake2ya::noise_hkdf2_blake2s(b"ck", b"ikm");
+    let (c, d, _) = blake2ya::noise_hkdf3_blake2s(b"ck", b"ikm");
+    assert_eq!((a, b), (c, d));
+    assert_eq!(blake2ya::noise_hmac_blake2b(b"key", &[b"ab", b"c"]), blake2ya::noise_hmac_blake2b(b"key", &[b"abc"]));
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "std")]
+
+use blake2ya::{Outboard, OutboardError};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+fn data(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i * 7 + i / 251) as u8).collect()
+}
+
+fn options(leaf: u32, digest: u8) -> Outboard {
+    let mut o = blake2ya::outboard();
+    o.leaf_length(leaf);
+    o.digest(digest);
+    o
+}
+
+fn encode(o: &Outboard, d: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut ob = Vec::new();
+    let root = o.encode(d, &mut ob).unwrap();
+    (root, ob)
+}
+
+fn error(e: std::io::Error) -> OutboardError {
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    *e.into_inner().unwrap().downcast::<OutboardError>().unwrap()
+}
+
+/// Root hash of the tree of 64-byte digests, where chaining values are the digests, with the public parameter block.
+fn model(d: &[u8], leaf: usize) -> Vec<u8> {
+    let node = |depth: u8, offset: usize, last: bool, data: &[Vec<u8>]| {
+        let mut p = blake2ya::blake2b_params();
+        p.digest(64);
+        p.fanout(2);
+        p.depth(255);
+        p.leaf_length(leaf as u32);
+        p.node_offset(offset as u64);
+        p.node_depth(depth);
+        p.inner_length(64);
+        let mut h = blake2ya::blake2b(p);
+        if last {
+            h.last_node();
+        }
+        data.iter().for_each(|x| h.update(x));
+        let mut r = vec![0; 64];
+        h.digest(&mut r);
+        r
+    };
+    let chunks = d.len().div_ceil(leaf).max(1);
+    let mut level: Vec<Vec<u8>> = (0..chunks)
+        .map(|j| node(0, j, j == chunks - 1, &[d[j * leaf..d.len().min((j + 1) * leaf)].to_vec()]))
+        .collect();
+    let mut depth = 1;
+    while level.len() > 1 {
+        let k = level.len().div_ceil(2);
+        level = level.chunks(2).enumerate().map(|(j, c)| node(depth, j, j == k - 1, c)).collect();
+        depth += 1;
+    }
+    level.remove(0)
+}
+
+#[test]
+fn outboard_root() {
+    for n in [0, 1, 63, 64, 65, 128, 129, 300, 1000] {
+        let d = data(n);
+        let (root, ob) = encode(&options(64, 64), &d);
+        assert_eq!(root, model(&d, 64), "{}", n);
+        let (mut k, mut nodes) = (n.div_ceil(64).max(1), 0);
+        while k > 1 {
+            nodes += k;
+            k = k.div_ceil(2);
+        }
+        assert_eq!(ob.len(), 8 + 64 * nodes, "{}", n);
+        assert_eq!(ob[ob.len() - 8..], (n as u64).to_le_bytes());
+    }
+    let (root, _) = encode(&blake2ya::outboard(), b"abc");
+    assert_eq!(root.len(), 32);
+    assert_ne!(root, encode(&options(1 << 14, 64), b"abc").0[..32]);
+}
+
+#[test]
+fn outboard_read() {
+    for n in [0, 1, 63, 64, 65, 200, 300, 700, 1000] {
+        let d = data(n);
+        let o = options(64, 32);
+        let (root, ob) = encode(&o, &d);
+        let mut r = o.reader(&root, Cursor::new(&d), Cursor::new(&ob)).unwrap();
+        assert_eq!(r.len(), n as u64);
+        let mut x = Vec::new();
+        r.read_to_end(&mut x).unwrap();
+        assert_eq!(x, d);
+        for (a, b) in [(0, n), (n / 3, n / 2), (n / 2, n), (n.saturating_sub(1), n)] {
+            let mut x = vec![0; b - a];
+            r.seek(SeekFrom::Start(a as u64)).unwrap();
+            r.read_exact(&mut x).unwrap();
+            assert_eq!(x, d[a..b]);
+        }
+        assert_eq!(r.seek(SeekFrom::End(0)).unwrap(), n as u64);
+        assert_eq!(r.read(&mut [0; 8]).unwrap(), 0);
+        assert!(r.seek(SeekFrom::Current(-(n as i64) - 1)).is_err());
+    }
+}
+
+#[test]
+fn outboard_tampered() {
+    let d = data(1000);
+    let o = options(64, 32);
+    let (root, ob) = encode(&o, &d);
+    let open = |d: &[u8], ob: &[u8], root: &[u8]| {
+        o.reader(root, Cursor::new(d.to_vec()), Cursor::new(ob.to_vec())).map_err(error)
+    };
+    let read = |d: &[u8], ob: &[u8], at: u64| {
+        let mut r = open(d, ob, &root)?;
+        r.seek(SeekFrom::Start(at)).unwrap();
+        r.read_exact(&mut [0; 10]).map_err(error)
+    };
+    assert_eq!(read(&d, &ob, 100), Ok(()));
+
+    // Leaves are only read when their bytes are.
+    let mut x = d.clone();
+    x[100] ^= 1;
+    assert_eq!(read(&x, &ob, 100), Err(OutboardError::Leaf(1)));
+    assert_eq!(read(&x, &ob, 200), Ok(()));
+
+    // 16 leaves, 8 nodes at depth 1, 4 at depth 2, 2 at depth 3, under the root at depth 4. In post-order, the node
+    // at depth 3 offset 0 comes after the 14 nodes below it, and the one at depth 2 offset 0 after 6. The last leaf is
+    // followed by its ancestors at depths 1 to 3.
+    let mut x = ob.clone();
+    x[64 * 14] ^= 1;
+    assert!(matches!(open(&d, &x, &root), Err(OutboardError::Root)));
+    let mut x = ob.clone();
+    x[64 * 6] ^= 1;
+    assert_eq!(read(&d, &x, 100), Err(OutboardError::Node(3, 0)));
+    assert_eq!(read(&d, &x, 600), Ok(()));
+    let mut x = ob.clone();
+    x[64 * 26] ^= 1;
+    assert!(matches!(open(&d, &x, &root), Err(OutboardError::Node(1, 7))));
+    let mut x = ob.clone();
+    x[64] ^= 1;
+    assert_eq!(read(&d, &x, 0), Err(OutboardError::Node(1, 0)));
+
+    let mut x = root.clone();
+    x[0] ^= 1;
+    assert!(matches!(open(&d, &ob, &x), Err(OutboardError::Root)));
+
+    // A forged length changes the size of the tree or the last leaf.
+    let n = ob.len();
+    for (len, e) in [
+        (999u64, OutboardError::Leaf(15)),
+        (1001, OutboardError::Truncated),
+        (1025, OutboardError::Length),
+        (0, OutboardError::Length),
+    ] {
+        let mut x = ob.clone();
+        x[n - 8..].copy_from_slice(&len.to_le_bytes());
+        assert!(matches!(open(&d, &x, &root), Err(y) if y == e), "{}", len);
+    }
+    let mut x = ob.clone();
+    x[n - 8..].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(matches!(open(&d, &x, &root), Err(OutboardError::Length)));
+
+    assert!(matches!(open(&d, &ob[..n - 1], &root), Err(OutboardError::Length)));
+    assert!(matches!(open(&d[..999], &ob, &root), Err(OutboardError::Truncated)));
+    assert!(matches!(open(&d, &ob[..4], &root), Err(OutboardError::Truncated)));
+}
+
+#[test]
+fn outboard_single_leaf() {
+    let o = options(64, 32);
+    let (root, ob) = encode(&o, b"abc");
+    assert_eq!(ob, 3u64.to_le_bytes());
+    assert!(matches!(o.reader(&root, Cursor::new(b"abd"), Cursor::new(&ob)).map_err(error), Err(OutboardError::Root)));
+}
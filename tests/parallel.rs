@@ -0,0 +1,101 @@
+#![cfg(feature = "rayon")]
+
+fn data() -> Vec<u8> {
+    (0..1 << 20).map(|i: u32| (i * 7 + 3) as u8).collect()
+}
+
+fn blake2bp_serial(m: &[u8]) -> [u8; 64] {
+    let mut p = blake2ya::blake2b_params();
+    p.digest(64);
+    let mut h = blake2ya::blake2bp(p);
+    h.update(m);
+    let mut r = [0; 64];
+    h.digest(&mut r);
+    r
+}
+
+fn blake2sp_serial(m: &[u8]) -> [u8; 32] {
+    let mut p = blake2ya::blake2s_params();
+    p.digest(32);
+    let mut h = blake2ya::blake2sp(p);
+    h.update(m);
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    r
+}
+
+#[test]
+fn parallel_blake2bp() {
+    let m = data();
+    for t in [0, 1, 3, 4] {
+        let mut c = blake2ya::parallel();
+        c.threads(t).unwrap();
+        c.threshold(0);
+        for n in [0, 1, 511, 512, 513, 100000, m.len()] {
+            let mut p = blake2ya::blake2b_params();
+            p.digest(64);
+            let mut h = blake2ya::blake2bp(p);
+            c.update_2bp(&mut h, &m[..n / 3]);
+            c.update_2bp(&mut h, &m[n / 3..n]);
+            let mut r = [0; 64];
+            h.digest(&mut r);
+            assert_eq!(r, blake2bp_serial(&m[..n]));
+        }
+    }
+}
+
+#[test]
+fn parallel_blake2sp() {
+    let m = data();
+    for t in [0, 1, 5, 8] {
+        let mut c = blake2ya::parallel();
+        c.threads(t).unwrap();
+        c.threshold(0);
+        for n in [0, 1, 511, 512, 513, 100000, m.len()] {
+            let mut p = blake2ya::blake2s_params();
+            p.digest(32);
+            let mut h = blake2ya::blake2sp(p);
+            c.update_2sp(&mut h, &m[..n / 3]);
+            c.update_2sp(&mut h, &m[n / 3..n]);
+            let mut r = [0; 32];
+            h.digest(&mut r);
+            assert_eq!(r, blake2sp_serial(&m[..n]));
+        }
+    }
+}
+
+#[test]
+fn parallel_read() {
+    let m = [data(), data(), data(), data(), data()].concat();
+    let c = blake2ya::parallel();
+    let mut p = blake2ya::blake2b_params();
+    p.digest(64);
+    let mut h = blake2ya::blake2bp(p);
+    c.read_2bp(&mut h, &m[..]).unwrap();
+    let mut r = [0; 64];
+    h.digest(&mut r);
+    assert_eq!(r, blake2bp_serial(&m));
+    let mut p = blake2ya::blake2s_params();
+    p.digest(32);
+    let mut h = blake2ya::blake2sp(p);
+    c.read_2sp(&mut h, &m[..]).unwrap();
+    let mut r = [0; 32];
+    h.digest(&mut r);
+    assert_eq!(r, blake2sp_serial(&m));
+}
+
+#[test]
+fn parallel_pow_search() {
+    let mut p = blake2ya::blake2b_params();
+    p.digest(8);
+    let w = blake2ya::pow(p, &[], &[7; 32]);
+    let threshold = 0xfff0000000000000;
+    let e = w.search(threshold, 1000, 1 << 20);
+    assert!(e.is_some());
+    for t in [0, 1, 3] {
+        let mut p = blake2ya::parallel();
+        p.threads(t).unwrap();
+        assert_eq!(p.search(&w, threshold, 1000, 1 << 20), e);
+        assert_eq!(p.search(&w, u64::MAX, 1000, 1 << 17), None);
+    }
+}
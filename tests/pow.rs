@@ -0,0 +1,62 @@
+/// Account of the Nano genesis block, the root of its first work.
+const GENESIS: &str = "e89208dd038fbb269987689621d52292ae9c35941a7484756ecced92a65093ba";
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+fn nano(root: &[u8]) -> blake2ya::Pow {
+    let mut p = blake2ya::blake2b_params();
+    p.digest(8);
+    blake2ya::pow(p, &[], root)
+}
+
+#[test]
+fn pow_nano() {
+    let w = nano(&unhex(GENESIS));
+    assert_eq!(w.work(0x62f05417dd3fb691), 0xfffffff4000d3dac);
+    assert!(w.check(0x62f05417dd3fb691, 0xffffffc000000000));
+    assert!(!w.check(0x62f05417dd3fb691, 0xfffffff800000000));
+    let mut d = [0; 8];
+    w.digest(0x62f05417dd3fb691, &mut d);
+    assert_eq!(d, 0xfffffff4000d3dacu64.to_le_bytes());
+}
+
+#[test]
+fn pow_midstate() {
+    let data: Vec<u8> = (0..500).map(|i: u32| (i * 7 + 3) as u8).collect();
+    for key in [&b""[..], b"key"] {
+        for a in [0, 1, 100, 119, 120, 121, 127, 128, 129, 200, 255, 256, 257, 300] {
+            for b in [0, 1, 32, 112, 113, 120] {
+                let mut p = blake2ya::blake2b_params();
+                p.digest(64);
+                p.key(key);
+                let w = blake2ya::pow(p.clone(), &data[..a], &data[a..a + b]);
+                for nonce in [0, 1, 0x0123456789abcdef, u64::MAX] {
+                    let mut h = blake2ya::blake2b(p.clone());
+                    h.update(&data[..a]);
+                    h.update(&u64::to_le_bytes(nonce));
+                    h.update(&data[a..a + b]);
+                    let mut e = [0; 64];
+                    h.digest(&mut e);
+                    let mut r = [0; 64];
+                    w.digest(nonce, &mut r);
+                    assert_eq!(r, e, "{} {} {}", a, b, nonce);
+                    assert_eq!(w.work(nonce), u64::from_le_bytes(e[..8].try_into().unwrap()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn pow_search() {
+    let w = nano(&[7; 32]);
+    let threshold = 0xff00000000000000;
+    let first = (0..).find(|&n| w.work(n) >= threshold).unwrap();
+    assert_eq!(w.search(threshold, 0, first + 1), Some(first));
+    assert_eq!(w.search(threshold, 0, first), None);
+    assert_eq!(w.search(threshold, u64::MAX - 1, first + 3), Some(first));
+    assert_eq!(w.search(0, 5, 1), Some(5));
+    assert_eq!(w.search(0, 5, 0), None);
+}
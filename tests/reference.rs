@@ -0,0 +1,191 @@
+//! Differential tests against the BLAKE2 reference C implementation, built from fixtures/blake2-ref, with randomized
+//! parameters, message lengths and update splits, and of BLAKE2X with known and unknown output lengths.
+
+/// xorshift64*, so that failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545f4914f6cdd1d)
+    }
+
+    /// An integer in [a, b].
+    fn range(&mut self, a: usize, b: usize) -> usize {
+        a + (self.next() % (b - a + 1) as u64) as usize
+    }
+
+    /// Random bytes, of a random length in [a, b].
+    fn bytes(&mut self, a: usize, b: usize) -> Vec<u8> {
+        let n = self.range(a, b);
+        (0..n).map(|_| self.next() as u8).collect()
+    }
+
+    /// Split data into randomly sized chunks, including empty ones.
+    fn chunks<'a>(&mut self, data: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut r = vec![];
+        let mut data = data;
+        while !data.is_empty() {
+            let n = match self.range(0, 3) {
+                0 => 0,
+                1 => self.range(1, 8),
+                2 => self.range(1, 200),
+                _ => self.range(1, data.len()),
+            };
+            let (a, b) = data.split_at(n.min(data.len()));
+            r.push(a);
+            data = b;
+        }
+        r
+    }
+}
+
+#[test]
+fn reference_blake2b() {
+    let mut rng = Rng(0x0123456789abcdef);
+    for _ in 0..2000 {
+        let digest = rng.range(1, 64);
+        let key = if rng.range(0, 1) == 0 { vec![] } else { rng.bytes(1, 64) };
+        let salt = rng.bytes(0, 16);
+        let person = rng.bytes(0, 16);
+        let fanout = rng.next() as u8;
+        let depth = rng.range(1, 255) as u8;
+        let leaf_length = rng.next() as u32;
+        let node_offset = rng.next();
+        let node_depth = rng.next() as u8;
+        let inner_length = rng.range(0, 64) as u8;
+        let last_node = rng.range(0, 1) == 1;
+        let data = match rng.range(0, 2) {
+            0 => rng.bytes(0, 300),
+            1 => rng.bytes(0, 4).repeat(128),
+            _ => rng.bytes(0, 5000),
+        };
+
+        let mut b = [0; 64];
+        b[0] = digest as u8;
+        b[1] = key.len() as u8;
+        b[2] = fanout;
+        b[3] = depth;
+        b[4..8].copy_from_slice(&leaf_length.to_le_bytes());
+        b[8..16].copy_from_slice(&node_offset.to_le_bytes());
+        b[16] = node_depth;
+        b[17] = inner_length;
+        b[32..32 + salt.len()].copy_from_slice(&salt);
+        b[48..48 + person.len()].copy_from_slice(&person);
+        let mut r = blake2_ref::Blake2b::new(&b, &key, last_node);
+        r.update(&data);
+        let e = r.finalize();
+
+        let mut p = blake2ya::blake2b_params();
+        p.digest(digest as u8);
+        p.key(&key);
+        p.salt(&salt);
+        p.person(&person);
+        p.fanout(fanout);
+        p.depth(depth);
+        p.leaf_length(leaf_length);
+        p.node_offset(node_offset);
+        p.node_depth(node_depth);
+        p.inner_length(inner_length);
+        let mut h = blake2ya::blake2b(p);
+        if last_node {
+            h.last_node();
+        }
+        for c in rng.chunks(&data) {
+            h.update(c);
+        }
+        let mut d = vec![0; digest];
+        h.digest(&mut d);
+        assert_eq!(d, e, "{} bytes, param block {:02x?}", data.len(), b);
+    }
+}
+
+#[test]
+fn reference_blake2s() {
+    let mut rng = Rng(0xfedcba9876543210);
+    for _ in 0..2000 {
+        let digest = rng.range(1, 32);
+        let key = if rng.range(0, 1) == 0 { vec![] } else { rng.bytes(1, 32) };
+        let salt = rng.bytes(0, 8);
+        let person = rng.bytes(0, 8);
+        let fanout = rng.next() as u8;
+        let depth = rng.range(1, 255) as u8;
+        let leaf_length = rng.next() as u32;
+        let node_offset = rng.next() >> 16;
+        let node_depth = rng.next() as u8;
+        let inner_length = rng.range(0, 32) as u8;
+        let last_node = rng.range(0, 1) == 1;
+        let data = match rng.range(0, 2) {
+            0 => rng.bytes(0, 300),
+            1 => rng.bytes(0, 4).repeat(64),
+            _ => rng.bytes(0, 5000),
+        };
+
+        let mut b = [0; 32];
+        b[0] = digest as u8;
+        b[1] = key.len() as u8;
+        b[2] = fanout;
+        b[3] = depth;
+        b[4..8].copy_from_slice(&leaf_length.to_le_bytes());
+        b[8..14].copy_from_slice(&node_offset.to_le_bytes()[..6]);
+        b[14] = node_depth;
+        b[15] = inner_length;
+        b[16..16 + salt.len()].copy_from_slice(&salt);
+        b[24..24 + person.len()].copy_from_slice(&person);
+        let mut r = blake2_ref::Blake2s::new(&b, &key, last_node);
+        r.update(&data);
+        let e = r.finalize();
+
+        let mut p = blake2ya::blake2s_params();
+        p.digest(digest as u8);
+        p.key(&key);
+        p.salt(&salt);
+        p.person(&person);
+        p.fanout(fanout);
+        p.depth(depth);
+        p.leaf_length(leaf_length);
+        p.node_offset(node_offset);
+        p.node_depth(node_depth);
+        p.inner_length(inner_length);
+        let mut h = blake2ya::blake2s(p);
+        if last_node {
+            h.last_node();
+        }
+        for c in rng.chunks(&data) {
+            h.update(c);
+        }
+        let mut d = vec![0; digest];
+        h.digest(&mut d);
+        assert_eq!(d, e, "{} bytes, param block {:02x?}", data.len(), b);
+    }
+}
+
+#[test]
+fn reference_blake2x() {
+    let mut rng = Rng(0x5eed0fb1a4e2c0de);
+    for _ in 0..500 {
+        let data = rng.bytes(0, 300);
+        let n = rng.range(1, 600);
+        let unknown = rng.range(0, 1) == 1;
+        let key = rng.bytes(0, 64);
+        let mut p = blake2ya::blake2b_params();
+        p.key(&key);
+        let mut h = blake2ya::blake2xb(p, if unknown { blake2ya::BLAKE2XB_UNKNOWN } else { n as u32 });
+        rng.chunks(&data).iter().for_each(|c| h.update(c));
+        let mut r = vec![0; n];
+        h.digest(&mut r);
+        let x = if unknown { u32::MAX } else { n as u32 };
+        assert_eq!(r, blake2_ref::blake2xb(x, n, &data, &key), "BLAKE2Xb of {} bytes, unknown {}", n, unknown);
+        let key = &key[..key.len().min(32)];
+        let mut p = blake2ya::blake2s_params();
+        p.key(key);
+        let mut h = blake2ya::blake2xs(p, if unknown { blake2ya::BLAKE2XS_UNKNOWN } else { n as u16 });
+        rng.chunks(&data).iter().for_each(|c| h.update(c));
+        let mut r = vec![0; n];
+        h.digest(&mut r);
+        let x = if unknown { u16::MAX } else { n as u16 };
+        assert_eq!(r, blake2_ref::blake2xs(x, n, &data, key), "BLAKE2Xs of {} bytes, unknown {}", n, unknown);
+    }
+}
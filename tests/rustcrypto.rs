@@ -0,0 +1,111 @@
+#![cfg(feature = "digest")]
+
+use digest::{Digest, FixedOutputReset, Mac, Update, VariableOutput};
+
+#[test]
+fn rustcrypto_blake2b_digest() {
+    let r = blake2ya::Blake2b512::digest(b"abc");
+    let e = [
+        0xba, 0x80, 0xa5, 0x3f, 0x98, 0x1c, 0x4d, 0x0d, 0x6a, 0x27, 0x97, 0xb6, 0x9f, 0x12, 0xf6, 0xe9, 0x4c, 0x21,
+        0x2f, 0x14, 0x68, 0x5a, 0xc4, 0xb7, 0x4b, 0x12, 0xbb, 0x6f, 0xdb, 0xff, 0xa2, 0xd1, 0x7d, 0x87, 0xc5, 0x39,
+        0x2a, 0xab, 0x79, 0x2d, 0xc2, 0x52, 0xd5, 0xde, 0x45, 0x33, 0xcc, 0x95, 0x18, 0xd3, 0x8a, 0xa8, 0xdb, 0xf1,
+        0x92, 0x5a, 0xb9, 0x23, 0x86, 0xed, 0xd4, 0x00, 0x99, 0x23,
+    ];
+    assert_eq!(r[..], e);
+}
+
+#[test]
+fn rustcrypto_blake2b_reset() {
+    let mut h = blake2ya::Blake2b256::new();
+    Digest::update(&mut h, b"xyz");
+    h.reset();
+    Digest::update(&mut h, b"abc");
+    let r = h.finalize_fixed_reset();
+    let e = [
+        0xbd, 0xdd, 0x81, 0x3c, 0x63, 0x42, 0x39, 0x72, 0x31, 0x71, 0xef, 0x3f, 0xee, 0x98, 0x57, 0x9b, 0x94, 0x96,
+        0x4e, 0x3b, 0xb1, 0xcb, 0x3e, 0x42, 0x72, 0x62, 0xc8, 0xc0, 0x68, 0xd5, 0x23, 0x19,
+    ];
+    assert_eq!(r[..], e);
+    Digest::update(&mut h, b"abc");
+    assert_eq!(h.finalize()[..], e);
+}
+
+#[test]
+fn rustcrypto_blake2b_var() {
+    let h = blake2ya::Blake2bVar::new(32).unwrap();
+    let mut r = [0; 32];
+    h.finalize_variable(&mut r).unwrap();
+    let e = [
+        0x0e, 0x57, 0x51, 0xc0, 0x26, 0xe5, 0x43, 0xb2, 0xe8, 0xab, 0x2e, 0xb0, 0x60, 0x99, 0xda, 0xa1, 0xd1, 0xe5,
+        0xdf, 0x47, 0x77, 0x8f, 0x77, 0x87, 0xfa, 0xab, 0x45, 0xcd, 0xf1, 0x2f, 0xe3, 0xa8,
+    ];
+    assert_eq!(r, e);
+    assert!(blake2ya::Blake2bVar::new(0).is_err());
+    assert!(blake2ya::Blake2bVar::new(65).is_err());
+    assert!(blake2ya::Blake2bVar::new(32).unwrap().finalize_variable(&mut [0; 64]).is_err());
+}
+
+#[test]
+fn rustcrypto_blake2b_mac() {
+    let k = (0..64).collect::<Vec<u8>>();
+    let m = (0..255).collect::<Vec<u8>>();
+    let e = [
+        0x14, 0x27, 0x09, 0xd6, 0x2e, 0x28, 0xfc, 0xcc, 0xd0, 0xaf, 0x97, 0xfa, 0xd0, 0xf8, 0x46, 0x5b, 0x97, 0x1e,
+        0x82, 0x20, 0x1d, 0xc5, 0x10, 0x70, 0xfa, 0xa0, 0x37, 0x2a, 0xa4, 0x3e, 0x92, 0x48, 0x4b, 0xe1, 0xc1, 0xe7,
+        0x3b, 0xa1, 0x09, 0x06, 0xd5, 0xd1, 0x85, 0x3d, 0xb6, 0xa4, 0x10, 0x6e, 0x0a, 0x7b, 0xf9, 0x80, 0x0d, 0x37,
+        0x3d, 0x6d, 0xee, 0x2d, 0x46, 0xd6, 0x2e, 0xf2, 0xa4, 0x61,
+    ];
+    let mut h = <blake2ya::Blake2bMac512 as Mac>::new_from_slice(&k).unwrap();
+    Mac::update(&mut h, &m);
+    assert_eq!(h.finalize_reset().into_bytes()[..], e);
+    Mac::update(&mut h, &m);
+    h.verify_slice(&e).unwrap();
+    assert!(<blake2ya::Blake2bMac512 as Mac>::new_from_slice(&[0; 65]).is_err());
+}
+
+#[test]
+fn rustcrypto_blake2s_digest() {
+    let r = blake2ya::Blake2s256::digest(b"abc");
+    let e = [
+        0x50, 0x8c, 0x5e, 0x8c, 0x32, 0x7c, 0x14, 0xe2, 0xe1, 0xa7, 0x2b, 0xa3, 0x4e, 0xeb, 0x45, 0x2f, 0x37, 0x45,
+        0x8b, 0x20, 0x9e, 0xd6, 0x3a, 0x29, 0x4d, 0x99, 0x9b, 0x4c, 0x86, 0x67, 0x59, 0x82,
+    ];
+    assert_eq!(r[..], e);
+    let mut h = blake2ya::Blake2sVar::new(32).unwrap();
+    h.update(b"abc");
+    let mut r = [0; 32];
+    h.finalize_variable(&mut r).unwrap();
+    assert_eq!(r, e);
+}
+
+#[test]
+fn rustcrypto_blake2s_mac() {
+    let k = (0..32).collect::<Vec<u8>>();
+    let m = (0..255).collect::<Vec<u8>>();
+    let e = [
+        0x3f, 0xb7, 0x35, 0x06, 0x1a, 0xbc, 0x51, 0x9d, 0xfe, 0x97, 0x9e, 0x54, 0xc1, 0xee, 0x5b, 0xfa, 0xd0, 0xa9,
+        0xd8, 0x58, 0xb3, 0x31, 0x5b, 0xad, 0x34, 0xbd, 0xe9, 0x99, 0xef, 0xd7, 0x24, 0xdd,
+    ];
+    let h = <blake2ya::Blake2sMac256 as Mac>::new_from_slice(&k).unwrap().chain_update(&m);
+    h.verify_slice(&e).unwrap();
+}
+
+#[test]
+fn rustcrypto_simple_hmac() {
+    let mut h = <hmac::SimpleHmac<blake2ya::Blake2b512> as Mac>::new_from_slice(b"key").unwrap();
+    Mac::update(&mut h, b"The quick brown fox jumps over the lazy dog");
+    let e = [
+        0x92, 0x29, 0x4f, 0x92, 0xc0, 0xdf, 0xb9, 0xb0, 0x0e, 0xc9, 0xae, 0x8b, 0xd9, 0x4d, 0x7e, 0x7d, 0x8a, 0x03,
+        0x6b, 0x88, 0x5a, 0x49, 0x9f, 0x14, 0x9d, 0xfe, 0x2f, 0xd2, 0x19, 0x93, 0x94, 0xaa, 0xaf, 0x6b, 0x88, 0x94,
+        0xa1, 0x73, 0x0c, 0xcc, 0xb2, 0xcd, 0x05, 0x0f, 0x9b, 0xcf, 0x50, 0x62, 0xa3, 0x8b, 0x51, 0xb0, 0xda, 0xb3,
+        0x32, 0x07, 0xf8, 0xef, 0x35, 0xae, 0x2c, 0x9d, 0xf5, 0x1b,
+    ];
+    h.verify_slice(&e).unwrap();
+    let mut h = <hmac::SimpleHmac<blake2ya::Blake2s256> as Mac>::new_from_slice(b"key").unwrap();
+    Mac::update(&mut h, b"The quick brown fox jumps over the lazy dog");
+    let e = [
+        0xf9, 0x32, 0x15, 0xbb, 0x90, 0xd4, 0xaf, 0x4c, 0x30, 0x61, 0xcd, 0x93, 0x2f, 0xb1, 0x69, 0xfb, 0x8b, 0xb8,
+        0xa9, 0x1d, 0x0b, 0x40, 0x22, 0xba, 0xea, 0x12, 0x71, 0xe1, 0x32, 0x3c, 0xd9, 0xa0,
+    ];
+    h.verify_slice(&e).unwrap();
+}
@@ -0,0 +1,9 @@
+#[test]
+fn selftest_pass() {
+    assert_eq!(blake2ya::selftest(), Ok(()));
+}
+
+#[test]
+fn selftest_error_display() {
+    assert_eq!(blake2ya::SelftestError::Blake2sRfc7693.to_string(), "BLAKE2s RFC 7693 self-test failed");
+}
@@ -0,0 +1,65 @@
+//! Keys and addresses of the Alice development account.
+
+const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+fn hex(d: &[u8]) -> String {
+    d.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn alice() -> [u8; 32] {
+    core::array::from_fn(|i| u8::from_str_radix(&ALICE[2 * i..2 * i + 2], 16).unwrap())
+}
+
+#[test]
+fn substrate_hashers() {
+    assert_eq!(hex(&blake2ya::blake2_128(b"")), "cae66941d9efbd404e4d88758ea67670");
+    assert_eq!(hex(&blake2ya::blake2_256(b"")), "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8");
+    assert_eq!(hex(&blake2ya::blake2_512(b"abc")[..8]), "ba80a53f981c4d0d");
+    // Suffix of the System.Account storage key of Alice.
+    let mut r = [0; 48];
+    blake2ya::blake2_128_concat(&alice(), &mut r);
+    assert_eq!(hex(&r), format!("de1e86a9a8c739864cf3cc5ec2bea59f{}", ALICE));
+}
+
+#[test]
+fn substrate_ss58() {
+    let cases = [
+        (42, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+        (0, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"),
+        (2, "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"),
+        (1284, "VdvKmYJfD4VXA9fzz1SbmCo2eYHSzUFbaDCZSuaNKJAe8YNg6"),
+        (16383, "yNa8JpqfFB3q8A29rCwSgxvdU94ufJw2yKKxDgznS5m1PoFvn"),
+    ];
+    for (prefix, address) in cases {
+        assert_eq!(blake2ya::ss58_encode(prefix, &alice()).unwrap().as_str(), address);
+        let mut key = [0; 32];
+        assert_eq!(blake2ya::ss58_decode(address, &mut key), Ok(prefix));
+        assert_eq!(key, alice());
+    }
+    let a = blake2ya::ss58_encode(42, &[0; 33]).unwrap();
+    assert_eq!(a.to_string(), "KVqMLDzVyHChtJ8imRTkP22Tuz8Yd7X9MABUhz1rHNpHny12V");
+    let mut key = [1; 33];
+    assert_eq!(blake2ya::ss58_decode(a.as_str(), &mut key), Ok(42));
+    assert_eq!(key, [0; 33]);
+}
+
+#[test]
+fn substrate_ss58_errors() {
+    use blake2ya::Ss58Error;
+    let mut key = [0; 32];
+    assert_eq!(blake2ya::ss58_encode(16384, &key), Err(Ss58Error::Prefix));
+    assert_eq!(blake2ya::ss58_encode(42, &key[..31]), Err(Ss58Error::Length));
+    assert_eq!(
+        blake2ya::ss58_decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ", &mut key),
+        Err(Ss58Error::Checksum)
+    );
+    assert_eq!(
+        blake2ya::ss58_decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQ0", &mut key),
+        Err(Ss58Error::Base58)
+    );
+    assert_eq!(
+        blake2ya::ss58_decode("KVqMLDzVyHChtJ8imRTkP22Tuz8Yd7X9MABUhz1rHNpHny12V", &mut key),
+        Err(Ss58Error::Length)
+    );
+    assert_eq!(blake2ya::ss58_decode("", &mut key), Err(Ss58Error::Prefix));
+}
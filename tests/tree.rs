@@ -0,0 +1,60 @@
+#![cfg(all(feature = "std", unix))]
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+fn dir(name: &str) -> PathBuf {
+    let d = std::env::temp_dir().join(format!("blake2ya-tree-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&d);
+    std::fs::create_dir_all(d.join("sub")).unwrap();
+    std::fs::write(d.join("a"), b"abc").unwrap();
+    std::fs::write(d.join("sub/b"), b"").unwrap();
+    std::fs::set_permissions(d.join("a"), std::fs::Permissions::from_mode(0o644)).unwrap();
+    std::fs::set_permissions(d.join("sub/b"), std::fs::Permissions::from_mode(0o755)).unwrap();
+    std::os::unix::fs::symlink("../a", d.join("sub/l")).unwrap();
+    d
+}
+
+fn hex(p: &Path) -> String {
+    blake2ya::tree(p, 32).unwrap().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn tree_digest() {
+    let d = dir("digest");
+    assert_eq!(hex(&d), "484e4db48a73526676ff2557101b4bd083254b458fe8760c3a1fe155fac844d9");
+    std::fs::remove_dir_all(&d).unwrap();
+}
+
+#[test]
+fn tree_changes() {
+    let d = dir("changes");
+    let e = dir("changes-copy");
+    let r = hex(&d);
+    assert_eq!(hex(&e), r);
+    std::fs::write(d.join("a"), b"abc").unwrap();
+    assert_eq!(hex(&d), r);
+    std::fs::set_permissions(d.join("a"), std::fs::Permissions::from_mode(0o600)).unwrap();
+    assert_eq!(hex(&d), r);
+    std::fs::set_permissions(d.join("a"), std::fs::Permissions::from_mode(0o744)).unwrap();
+    assert_ne!(hex(&d), r);
+    std::fs::set_permissions(d.join("a"), std::fs::Permissions::from_mode(0o644)).unwrap();
+    std::fs::rename(d.join("sub/b"), d.join("sub/c")).unwrap();
+    assert_ne!(hex(&d), r);
+    std::fs::rename(d.join("sub/c"), d.join("sub/b")).unwrap();
+    std::fs::remove_file(d.join("sub/l")).unwrap();
+    std::os::unix::fs::symlink("a", d.join("sub/l")).unwrap();
+    assert_ne!(hex(&d), r);
+    std::fs::remove_file(d.join("sub/l")).unwrap();
+    std::fs::create_dir(d.join("sub/l")).unwrap();
+    assert_ne!(hex(&d), r);
+    std::fs::remove_dir_all(&d).unwrap();
+    std::fs::remove_dir_all(&e).unwrap();
+}
+
+#[test]
+fn tree_error() {
+    let e = blake2ya::tree(Path::new("/nonexistent/blake2ya"), 64).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+    assert!(e.to_string().starts_with("/nonexistent/blake2ya: "));
+}